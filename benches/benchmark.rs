@@ -1,5 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
-use naturalneighbor::{Interpolator, Point};
+use naturalneighbor::{GridSpec, Interpolator, Point};
 use rand::Rng;
 
 fn benchmark(c: &mut Criterion) {
@@ -27,6 +27,21 @@ fn benchmark(c: &mut Criterion) {
             );
         })
     });
+
+    c.bench_function("interpolate_grid", |b| {
+        b.iter(|| {
+            let _ = interpolator.interpolate_grid(
+                &weights,
+                GridSpec {
+                    origin: Point { x: 0.0, y: 0.0 },
+                    dx: bound / 100.0,
+                    dy: bound / 100.0,
+                    nx: 100,
+                    ny: 100,
+                },
+            );
+        })
+    });
 }
 
 criterion_group!(benches, benchmark);
@@ -1,5 +1,7 @@
+#![allow(clippy::wrong_self_convention, clippy::unnecessary_cast)]
+
 use image::{ImageBuffer, Rgb};
-use naturalneighbor::{Interpolator, Lerpable, Point};
+use naturalneighbor::{GridSpec, Interpolator, Lerpable, Point};
 use rand::Rng;
 
 #[derive(Copy, Clone, Debug)]
@@ -90,22 +92,20 @@ fn main() {
         .collect::<Vec<_>>();
 
     let interpolator = Interpolator::new(&points);
-    // Draw the interpolated colors on the image
-    for x in 0..img_w {
-        for y in 0..img_h {
-            let c = interpolator
-                .interpolate(
-                    &colors,
-                    Point {
-                        x: x as f64,
-                        y: y as f64,
-                    },
-                )
-                .unwrap();
+    let grid = GridSpec {
+        origin: Point { x: 0.0, y: 0.0 },
+        dx: 1.0,
+        dy: 1.0,
+        nx: img_w as usize,
+        ny: img_h as usize,
+    };
+    let interpolated = interpolator.interpolate_grid(&colors, grid).unwrap();
 
-            if let Some(c) = c {
-                img.put_pixel(x as u32, y as u32, c.to_rgb());
-            }
+    // Draw the interpolated colors on the image
+    for (i, c) in interpolated.into_iter().enumerate() {
+        let (x, y) = ((i % img_w as usize) as u32, (i / img_w as usize) as u32);
+        if let Some(c) = c {
+            img.put_pixel(x, y, c.to_rgb());
         }
     }
 
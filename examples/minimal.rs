@@ -1,5 +1,7 @@
+#![allow(clippy::unnecessary_cast)]
+
 use image::{ImageBuffer, Rgb};
-use naturalneighbor::{Interpolator, Point};
+use naturalneighbor::{GridSpec, Interpolator, Point};
 fn main() {
     let (img_w, img_h) = (800, 800);
 
@@ -29,26 +31,25 @@ fn main() {
     // Create an interpolator
     let interpolator = Interpolator::new(&points);
 
+    // Interpolate the values at all the pixels
+    let grid = GridSpec {
+        origin: Point { x: 0.0, y: 0.0 },
+        dx: 1.0,
+        dy: 1.0,
+        nx: img_w as usize,
+        ny: img_h as usize,
+    };
+    let values = interpolator.interpolate_grid(&weights, grid).unwrap();
+
     // Draw the interpolated colors on the image
-    for x in 0..img_w {
-        for y in 0..img_h {
-            let v = interpolator
-                .interpolate(
-                    &weights,
-                    Point {
-                        x: x as f64,
-                        y: y as f64,
-                    },
-                )
-                .unwrap();
-
-            if let Some(v) = v {
-                img.put_pixel(
-                    x as u32,
-                    y as u32,
-                    Rgb([(v * 255.0) as u8, (v * 255.0) as u8, (v * 255.0) as u8]),
-                );
-            }
+    for (i, v) in values.into_iter().enumerate() {
+        let (x, y) = ((i % img_w as usize) as u32, (i / img_w as usize) as u32);
+        if let Some(v) = v {
+            img.put_pixel(
+                x,
+                y,
+                Rgb([(v * 255.0) as u8, (v * 255.0) as u8, (v * 255.0) as u8]),
+            );
         }
     }
 
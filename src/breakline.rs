@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    util::{in_circumcircle, next_harfedge, orient, prev_harfedge},
+    Interpolator, InterpolatorError, Point,
+};
+
+/// How a breakline leaves one of its sites.
+enum Departure {
+    /// The breakline is already an edge of the triangulation.
+    Edge,
+    /// The breakline passes through the site, along an edge of the triangulation.
+    Site(usize),
+    /// The breakline crosses the harfedge.
+    Crossing(usize),
+}
+
+/// The key of the breakline between the sites, independent of the direction.
+pub(crate) fn breakline_key(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+impl Interpolator {
+    /// Create a new Interpolator from a slice of points and the breaklines between them.
+    ///
+    /// A breakline is given as a pair of the indices of the points, and it is always an edge of the triangulation
+    /// (constrained Delaunay triangulation).
+    /// The values are not blended across the breaklines:
+    /// the natural neighbors of a point are only searched on the same side of the breaklines,
+    /// and the sites on the far side are excluded from the weights.
+    ///
+    /// A breakline passing through other sites is split at them.
+    /// Returns `InterpolatorError::InvalidSiteIndex` if a breakline refers to a point which does not exist,
+    /// and `InterpolatorError::CrossingBreaklines` if two breaklines cross each other.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    ///     Point { x: 0.0, y: 2.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 2.0 },
+    /// ];
+    /// let values = [0.0f64, 10.0, 10.0, 0.0, 5.0, 5.0];
+    ///
+    /// // a cliff between (1.0, 0.0) and (1.0, 2.0)
+    /// let interpolator = Interpolator::new_with_breaklines(&points, &[(4, 5)]).unwrap();
+    ///
+    /// let weights = interpolator
+    ///     .query_weights(Point { x: 0.9, y: 1.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!(weights.iter().all(|(i, _)| *i != 1 && *i != 2));
+    /// ```
+    pub fn new_with_breaklines<P>(
+        points: &[P],
+        breaklines: &[(usize, usize)],
+    ) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let mut interpolator = Self::new(points);
+        for (a, b) in breaklines {
+            for i in [a, b] {
+                if *i >= interpolator.points.len() {
+                    return Err(InterpolatorError::InvalidSiteIndex(*i));
+                }
+            }
+            let (a, b) = (interpolator.merged[*a], interpolator.merged[*b]);
+            if interpolator.triangles.is_empty() {
+                // the breaklines are enforced when the sites are triangulated
+                if a != b {
+                    interpolator.breaklines.insert(breakline_key(a, b));
+                }
+            } else {
+                interpolator.insert_breakline(a, b)?;
+            }
+        }
+        Ok(interpolator)
+    }
+
+    /// The breaklines in the triangulation, as pairs of the indices of the sites in ascending order.
+    ///
+    /// The breaklines split at the sites on them are returned as the separate segments.
+    pub fn breaklines(&self) -> Vec<(usize, usize)> {
+        let mut breaklines = self.breaklines.iter().copied().collect::<Vec<_>>();
+        breaklines.sort_unstable();
+        breaklines
+    }
+
+    /// Check if the harfedge is on a breakline.
+    pub(crate) fn is_breakline(&self, e: usize) -> bool {
+        !self.breaklines.is_empty()
+            && self.breaklines.contains(&breakline_key(
+                self.triangles[e],
+                self.triangles[next_harfedge(e)],
+            ))
+    }
+
+    /// Check if the edge between the sites is a breakline.
+    pub(crate) fn is_breakline_between(&self, a: usize, b: usize) -> bool {
+        self.breaklines.contains(&breakline_key(a, b))
+    }
+
+    /// Enforce the breaklines again after the sites are triangulated from scratch.
+    pub(crate) fn restore_breaklines(&mut self) {
+        if self.triangles.is_empty() {
+            return;
+        }
+        let mut breaklines = self.breaklines.iter().copied().collect::<Vec<_>>();
+        breaklines.sort_unstable();
+        for (a, b) in breaklines {
+            // the breaklines have been checked not to cross each other
+            let _ = self.insert_breakline(a, b);
+        }
+    }
+
+    /// Remove the breaklines connected to the site to be removed.
+    /// If the site splits a breakline into two collinear segments, the breakline between their other ends is returned,
+    /// which should be inserted again after the site is removed.
+    pub(crate) fn detach_breaklines(&mut self, index: usize) -> Option<(usize, usize)> {
+        let connected = self
+            .breaklines
+            .iter()
+            .filter(|(a, b)| *a == index || *b == index)
+            .copied()
+            .collect::<Vec<_>>();
+        for key in &connected {
+            self.breaklines.remove(key);
+        }
+
+        let ends = connected
+            .iter()
+            .map(|(a, b)| if *a == index { *b } else { *a })
+            .collect::<Vec<_>>();
+        if let [a, b] = ends[..] {
+            let (pa, pb, p) = (&self.points[a], &self.points[b], &self.points[index]);
+            let dot = (pa.x - p.x) * (pb.x - p.x) + (pa.y - p.y) * (pb.y - p.y);
+            if orient(pa, pb, p) == 0. && dot < 0. {
+                return Some((a, b));
+            }
+        }
+        None
+    }
+
+    /// Rename the site of the breaklines, when the site is replaced by another site at the same position.
+    pub(crate) fn rename_breaklines(&mut self, from: usize, to: usize) {
+        let connected = self
+            .breaklines
+            .iter()
+            .filter(|(a, b)| *a == from || *b == from)
+            .copied()
+            .collect::<Vec<_>>();
+        for (a, b) in connected {
+            self.breaklines.remove(&(a, b));
+            let other = if a == from { b } else { a };
+            self.breaklines.insert(breakline_key(other, to));
+        }
+    }
+
+    /// Split the breakline under the new site, if any, so that the new site can be connected to the sites on both sides.
+    /// `it` is the triangle containing the new site.
+    /// Returns the ends of the split breakline.
+    pub(crate) fn split_breakline(&mut self, ptarget: &Point, it: usize) -> Option<(usize, usize)> {
+        let e = (it * 3..it * 3 + 3).find(|e| {
+            let (a, b) = (self.triangles[*e], self.triangles[next_harfedge(*e)]);
+            self.is_breakline(*e) && orient(&self.points[a], &self.points[b], ptarget) == 0.
+        })?;
+        let (a, b) = (self.triangles[e], self.triangles[next_harfedge(e)]);
+        self.breaklines.remove(&breakline_key(a, b));
+        Some((a, b))
+    }
+
+    /// Insert the breakline between the sites into the triangulation.
+    ///
+    /// The triangles crossed by the breakline are removed,
+    /// and the polygons on both sides of the breakline are triangulated again.
+    pub(crate) fn insert_breakline(&mut self, a: usize, b: usize) -> Result<(), InterpolatorError> {
+        let mut from = a;
+        while from != b {
+            let to = match self.departure(from, b) {
+                Some(Departure::Edge) => b,
+                Some(Departure::Site(c)) => c,
+                Some(Departure::Crossing(e)) => self.cross_triangles(from, b, e)?,
+                // the breakline cannot be found in the triangulation (this should not happen for a valid triangulation)
+                None => return Ok(()),
+            };
+            self.breaklines.insert(breakline_key(from, to));
+            from = to;
+        }
+        Ok(())
+    }
+
+    /// Find how the breakline from the site `from` to the site `to` leaves `from`.
+    fn departure(&self, from: usize, to: usize) -> Option<Departure> {
+        let (pfrom, pto) = (&self.points[from], &self.points[to]);
+        let star = self
+            .tree
+            .locate_all_at_point(&[pfrom.x, pfrom.y])
+            .map(|t| t.itriangle())
+            .filter(|it| self.triangles[it * 3..it * 3 + 3].contains(&from))
+            .collect::<Vec<_>>();
+
+        for it in star {
+            let k = (0..3).find(|k| self.triangles[it * 3 + k] == from).unwrap();
+            // the edge opposite to `from`
+            let e = it * 3 + (k + 1) % 3;
+            let (v1, v2) = (self.triangles[e], self.triangles[next_harfedge(e)]);
+            if v1 == to || v2 == to {
+                return Some(Departure::Edge);
+            }
+
+            let (p1, p2) = (&self.points[v1], &self.points[v2]);
+            for (v, p) in [(v1, p1), (v2, p2)] {
+                let dot = (p.x - pfrom.x) * (pto.x - pfrom.x) + (p.y - pfrom.y) * (pto.y - pfrom.y);
+                if orient(pfrom, pto, p) == 0. && dot > 0. {
+                    return Some(Departure::Site(v));
+                }
+            }
+
+            let (o1, o2) = (orient(pfrom, pto, p1), orient(pfrom, pto, p2));
+            if o1 * o2 < 0. && orient(p1, p2, pfrom) * orient(p1, p2, pto) < 0. {
+                return Some(Departure::Crossing(e));
+            }
+        }
+        None
+    }
+
+    /// Remove the triangles crossed by the breakline from the site `from` to the site `to`, starting from the harfedge `e`,
+    /// and triangulate the polygons on both sides of the breakline.
+    /// The crossing stops at `to` or at the first site on the breakline, which is returned.
+    fn cross_triangles(
+        &mut self,
+        from: usize,
+        to: usize,
+        e: usize,
+    ) -> Result<usize, InterpolatorError> {
+        let (pfrom, pto) = (self.points[from].clone(), self.points[to].clone());
+
+        // the sites on the left and the right side of the breakline, in the order along it
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        let mut push_side = |v: usize, points: &[Point]| {
+            if orient(&pfrom, &pto, &points[v]) > 0. {
+                left.push(v);
+            } else {
+                right.push(v);
+            }
+        };
+        push_side(self.triangles[e], &self.points);
+        push_side(self.triangles[next_harfedge(e)], &self.points);
+
+        let mut region = vec![e / 3];
+        let mut e = e;
+        let end = loop {
+            if self.is_breakline(e) {
+                return Err(InterpolatorError::CrossingBreaklines(
+                    breakline_key(from, to),
+                    breakline_key(self.triangles[e], self.triangles[next_harfedge(e)]),
+                ));
+            }
+            let opposite = self.harfedges[e];
+            if opposite >= self.harfedges.len() {
+                // the breakline leaves the triangulation (this should not happen for a valid triangulation)
+                return Ok(to);
+            }
+            region.push(opposite / 3);
+
+            let w = self.triangles[prev_harfedge(opposite)];
+            let ow = orient(&pfrom, &pto, &self.points[w]);
+            if w == to || ow == 0. {
+                break w;
+            }
+            push_side(w, &self.points);
+
+            // the breakline leaves the triangle through the edge between `w` and the site on the other side
+            let v1 = self.triangles[next_harfedge(opposite)];
+            e = if (orient(&pfrom, &pto, &self.points[v1]) > 0.) == (ow > 0.) {
+                prev_harfedge(opposite)
+            } else {
+                next_harfedge(opposite)
+            };
+        };
+
+        // the edges on the boundary of the region, as start -> (end, opposite harfedge)
+        let mut link = HashMap::new();
+        let inside = region.iter().copied().collect::<HashSet<_>>();
+        for it in &region {
+            for e in it * 3..it * 3 + 3 {
+                let opposite = self.harfedges[e];
+                if opposite >= self.harfedges.len() || !inside.contains(&(opposite / 3)) {
+                    link.insert(
+                        self.triangles[e],
+                        (self.triangles[next_harfedge(e)], opposite),
+                    );
+                }
+            }
+        }
+
+        let mut triangles = Vec::new();
+        self.triangulate_pseudo_polygon(from, end, &left, &mut triangles);
+        self.triangulate_pseudo_polygon(from, end, &right, &mut triangles);
+        self.replace_star(&region, &triangles, &link);
+        Ok(end)
+    }
+
+    /// Triangulate the polygon made of the edge between `a` and `b` and the chain of the sites between them,
+    /// so that the circumcircle of each triangle contains no site of the chain.
+    fn triangulate_pseudo_polygon(
+        &self,
+        a: usize,
+        b: usize,
+        chain: &[usize],
+        triangles: &mut Vec<[usize; 3]>,
+    ) {
+        if chain.is_empty() {
+            return;
+        }
+        let mut c = 0;
+        for i in 1..chain.len() {
+            let triangle = [&self.points[a], &self.points[b], &self.points[chain[c]]];
+            if in_circumcircle(&triangle, &self.points[chain[i]]) {
+                c = i;
+            }
+        }
+        self.triangulate_pseudo_polygon(a, chain[c], &chain[..c], triangles);
+        self.triangulate_pseudo_polygon(chain[c], b, &chain[c + 1..], triangles);
+
+        // the triangles are clockwise
+        if orient(&self.points[a], &self.points[b], &self.points[chain[c]]) > 0. {
+            triangles.push([a, chain[c], b]);
+        } else {
+            triangles.push([a, b, chain[c]]);
+        }
+    }
+}
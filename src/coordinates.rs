@@ -0,0 +1,57 @@
+use crate::Point;
+
+/// Defines the kind of the natural neighbor coordinates used as the weights of the sites.
+///
+/// The kind is set with `Interpolator::with_coordinate_kind`.
+/// The default is `CoordinateKind::Sibson`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CoordinateKind {
+    /// Sibson coordinates: the area of the voronoi cell stolen from each site by the target point.
+    #[default]
+    Sibson,
+    /// Laplace (non-Sibsonian) coordinates: the length of the voronoi edge between the target point and each site,
+    /// divided by the distance between them.
+    /// These are cheaper than Sibson coordinates since no area is calculated.
+    Laplace,
+}
+
+/// Calculate the Laplace weight of the site and its gradient with respect to the target point.
+///
+/// `start` and `end` are the endpoints of the voronoi edge between the target point and the site,
+/// ordered counterclockwise around the target point.
+/// `vstart` and `vend` are the derivatives of the endpoints (see `util::circumcenter_derivative`).
+pub(crate) fn laplace_weight(
+    ptarget: &Point,
+    site: &Point,
+    start: &Point,
+    end: &Point,
+    vstart: [f64; 2],
+    vend: [f64; 2],
+) -> (f64, [f64; 2]) {
+    let dx = site.x - ptarget.x;
+    let dy = site.y - ptarget.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    // the direction of the voronoi edge (counterclockwise around the target point)
+    let t = [-dy / distance, dx / distance];
+
+    // signed length of the voronoi edge
+    let length = (end.x - start.x) * t[0] + (end.y - start.y) * t[1];
+
+    let sstart = vstart[0] * t[0] + vstart[1] * t[1];
+    let send = vend[0] * t[0] + vend[1] * t[1];
+    let length_gradient = [
+        (end.x - ptarget.x) * send - (start.x - ptarget.x) * sstart,
+        (end.y - ptarget.y) * send - (start.y - ptarget.y) * sstart,
+    ];
+
+    // d(distance)/d(ptarget) = (ptarget - site) / distance
+    let d3 = distance * distance * distance;
+    let gradient = [
+        length_gradient[0] / distance + length * dx / d3,
+        length_gradient[1] / distance + length * dy / d3,
+    ];
+
+    (length / distance, gradient)
+}
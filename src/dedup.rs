@@ -0,0 +1,159 @@
+use std::collections::HashMap;
+
+use rstar::{primitives::GeomWithData, RTree};
+
+use crate::{Interpolator, InterpolatorError, Point};
+
+/// Defines how the values of the sites closer than the tolerance are merged.
+///
+/// Used by `Interpolator::new_with_deduplication`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MergePolicy {
+    /// Only the value of the first site is used.
+    #[default]
+    KeepFirst,
+    /// The average of the values of the merged sites is used.
+    Average,
+    /// `InterpolatorError::DuplicatedSites` is returned.
+    Error,
+}
+
+/// Find the site which each point is merged into.
+/// A point is merged into the nearest preceding site within the tolerance, if any.
+pub(crate) fn merge_duplicates(points: &[Point], tolerance: f64) -> Vec<usize> {
+    if tolerance <= 0. {
+        // (0.0 + x) turns -0.0 into 0.0
+        let mut sites = HashMap::new();
+        return points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                *sites
+                    .entry(((0. + p.x).to_bits(), (0. + p.y).to_bits()))
+                    .or_insert(i)
+            })
+            .collect();
+    }
+
+    let mut sites = RTree::<GeomWithData<[f64; 2], usize>>::new();
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let nearest = sites
+                .nearest_neighbor_iter_with_distance_2(&[p.x, p.y])
+                .next()
+                .filter(|(_, d2)| *d2 <= tolerance * tolerance)
+                .map(|(site, _)| site.data);
+            nearest.unwrap_or_else(|| {
+                sites.insert(GeomWithData::new([p.x, p.y], i));
+                i
+            })
+        })
+        .collect()
+}
+
+impl Interpolator {
+    /// Create a new Interpolator from a slice of points, merging the points closer than the tolerance.
+    ///
+    /// Each point is merged into the nearest preceding site within the tolerance,
+    /// and only the sites which are not merged are triangulated.
+    /// The indices of the points are kept, so the values passed to `interpolate` are the same as `Interpolator::new`,
+    /// and the values of the merged sites are used by the merge policy (see [MergePolicy]).
+    ///
+    /// `Interpolator::new` merges only the points at exactly the same position with `MergePolicy::KeepFirst`.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, MergePolicy, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    ///     Point { x: 1.0, y: 1.0 + 1e-9 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0, 4.0];
+    ///
+    /// let interpolator =
+    ///     Interpolator::new_with_deduplication(&points, 1e-6, MergePolicy::Average).unwrap();
+    /// assert_eq!(interpolator.merged_sites(), &[0, 1, 2, 3, 2]);
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 1.0, y: 1.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 3.0).abs() < 1e-6);
+    ///
+    /// assert!(
+    ///     Interpolator::new_with_deduplication(&points, 1e-6, MergePolicy::Error).is_err()
+    /// );
+    /// ```
+    pub fn new_with_deduplication<P>(
+        points: &[P],
+        tolerance: f64,
+        policy: MergePolicy,
+    ) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let points = points
+            .iter()
+            .map(|p| (*p).clone().into())
+            .collect::<Vec<Point>>();
+
+        let merged = merge_duplicates(&points, tolerance);
+        if policy == MergePolicy::Error {
+            if let Some((i, site)) = merged.iter().enumerate().find(|(i, site)| i != *site) {
+                return Err(InterpolatorError::DuplicatedSites(*site, i));
+            }
+        }
+
+        Ok(Self::from_merged_sites(points, merged, tolerance, policy))
+    }
+
+    /// The mapping from the indices of the points to the sites which they are merged into.
+    ///
+    /// The element is the index of the point itself if it is not merged.
+    pub fn merged_sites(&self) -> &[usize] {
+        &self.merged
+    }
+
+    /// Merge the site `index` (not in the triangulation) into the site `site`.
+    pub(crate) fn merge_into(&mut self, index: usize, site: usize) {
+        self.merged[index] = site;
+        self.groups
+            .entry(site)
+            .or_insert_with(|| vec![site])
+            .push(index);
+    }
+
+    /// Apply the weight of the site to the sites merged into it, by the merge policy.
+    pub(crate) fn apply_merged_weight(
+        &self,
+        i: usize,
+        weight: f64,
+        gradient: [f64; 2],
+        tmp_weight_sum: f64,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) {
+        if self.merge_policy == MergePolicy::Average {
+            if let Some(group) = self.groups.get(&i) {
+                let n = group.len() as f64;
+                let previous_sum = tmp_weight_sum - weight;
+                for (k, j) in group.iter().enumerate() {
+                    apply_weight(
+                        *j,
+                        weight / n,
+                        [gradient[0] / n, gradient[1] / n],
+                        previous_sum + weight * (k + 1) as f64 / n,
+                    );
+                }
+                return;
+            }
+        }
+        apply_weight(i, weight, gradient, tmp_weight_sum);
+    }
+}
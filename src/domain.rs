@@ -0,0 +1,232 @@
+use rstar::{
+    primitives::{GeomWithData, Line},
+    RTree, AABB,
+};
+
+use crate::{util::orient, Interpolator, Point};
+
+// The edge of the ring, labeled with the index of the ring (0 for the outer boundary) and the index of its first vertex.
+type RingEdge = GeomWithData<Line<[f64; 2]>, (usize, usize)>;
+
+/// Defines the region where the values are interpolated, as an outer boundary polygon with holes.
+///
+/// The polygons are given as the rings of the vertices, in either orientation and without repeating the first vertex.
+/// The points on the boundary are inside the domain.
+/// The edges of the polygons are indexed in an RTree, so a detailed boundary does not slow down each query.
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Domain, Point};
+///
+/// let domain = Domain::new(&[
+///     Point { x: 0.0, y: 0.0 },
+///     Point { x: 4.0, y: 0.0 },
+///     Point { x: 4.0, y: 4.0 },
+///     Point { x: 0.0, y: 4.0 },
+/// ])
+/// .with_hole(&[
+///     Point { x: 1.0, y: 1.0 },
+///     Point { x: 2.0, y: 1.0 },
+///     Point { x: 2.0, y: 2.0 },
+///     Point { x: 1.0, y: 2.0 },
+/// ]);
+///
+/// assert!(domain.contains(&Point { x: 3.0, y: 3.0 }));
+/// assert!(!domain.contains(&Point { x: 1.5, y: 1.5 }));
+/// assert!(!domain.contains(&Point { x: 5.0, y: 1.5 }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Domain {
+    outer: Vec<Point>,
+    holes: Vec<Vec<Point>>,
+    edges: RTree<RingEdge>,
+}
+
+impl Domain {
+    /// Create a new domain from the outer boundary polygon.
+    pub fn new<P>(outer: &[P]) -> Self
+    where
+        P: Into<Point> + Clone,
+    {
+        let outer = outer
+            .iter()
+            .map(|p| (*p).clone().into())
+            .collect::<Vec<_>>();
+        Self {
+            edges: RTree::bulk_load(ring_edges(&outer, 0).collect()),
+            outer,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Add a hole to the domain.
+    pub fn with_hole<P>(mut self, hole: &[P]) -> Self
+    where
+        P: Into<Point> + Clone,
+    {
+        let hole = hole.iter().map(|p| (*p).clone().into()).collect::<Vec<_>>();
+        for edge in ring_edges(&hole, self.holes.len() + 1) {
+            self.edges.insert(edge);
+        }
+        self.holes.push(hole);
+        self
+    }
+
+    /// The ring of the index: 0 for the outer boundary, and k + 1 for the hole k.
+    fn ring(&self, k: usize) -> &[Point] {
+        if k == 0 {
+            &self.outer
+        } else {
+            &self.holes[k - 1]
+        }
+    }
+
+    /// The outer boundary polygon and the holes.
+    #[cfg(feature = "serde")]
+    pub(crate) fn rings(&self) -> (&[Point], &[Vec<Point>]) {
+        (&self.outer, &self.holes)
+    }
+
+    /// The nearest point on the boundary of the domain (including the holes) from the point.
+    pub(crate) fn nearest_boundary_point(&self, p: &Point) -> Point {
+        self.edges.nearest_neighbor(&[p.x, p.y]).map_or_else(
+            || p.clone(),
+            |edge| {
+                let q = edge.geom().nearest_point(&[p.x, p.y]);
+                Point { x: q[0], y: q[1] }
+            },
+        )
+    }
+
+    /// Check if the point is inside the domain (including its boundary).
+    pub fn contains(&self, p: &Point) -> bool {
+        // Only the edges whose bounding boxes meet the horizontal ray from the point to the right
+        // can cross the ray or have the point on them.
+        let mut locations = vec![RingLocation::Outside; self.holes.len() + 1];
+        let ray = AABB::from_corners([p.x, p.y], [f64::MAX, p.y]);
+        for edge in self.edges.locate_in_envelope_intersecting(&ray) {
+            let (k, i) = edge.data;
+            let ring = self.ring(k);
+            let (a, b) = (&ring[i], &ring[(i + 1) % ring.len()]);
+            locations[k] = match (locations[k], ray_crossing(a, b, p)) {
+                (RingLocation::Boundary, _) | (_, RayCrossing::OnEdge) => RingLocation::Boundary,
+                (RingLocation::Inside, RayCrossing::Crosses) => RingLocation::Outside,
+                (RingLocation::Outside, RayCrossing::Crosses) => RingLocation::Inside,
+                (location, RayCrossing::Misses) => location,
+            };
+        }
+
+        match locations[0] {
+            RingLocation::Outside => false,
+            RingLocation::Boundary => true,
+            RingLocation::Inside => locations[1..]
+                .iter()
+                .all(|location| *location != RingLocation::Inside),
+        }
+    }
+}
+
+/// The edges of the ring labeled with the index of the ring.
+fn ring_edges(ring: &[Point], k: usize) -> impl Iterator<Item = RingEdge> + '_ {
+    ring.iter().enumerate().map(move |(i, a)| {
+        let b = &ring[(i + 1) % ring.len()];
+        GeomWithData::new(Line::new([a.x, a.y], [b.x, b.y]), (k, i))
+    })
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum RingLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+enum RayCrossing {
+    OnEdge,
+    Crosses,
+    Misses,
+}
+
+/// Find how the edge meets the horizontal ray from the point to the right, for the even-odd rule.
+fn ray_crossing(a: &Point, b: &Point, p: &Point) -> RayCrossing {
+    let o = orient(a, b, p);
+    if o == 0.
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+    {
+        RayCrossing::OnEdge
+    } else if (a.y > p.y) != (b.y > p.y) && (o > 0.) == (b.y > a.y) {
+        RayCrossing::Crosses
+    } else {
+        RayCrossing::Misses
+    }
+}
+
+impl Interpolator {
+    /// Set the domain where the values are interpolated.
+    ///
+    /// The points outside the domain are treated as the points outside the convex hull of the sites:
+    /// they are not interpolated unless an extrapolation mode is set (see `with_extrapolation_mode`).
+    /// They are extrapolated from the sites inside the domain:
+    /// `NearestSite` uses the nearest site inside the domain,
+    /// `NearestHullEdge` uses the weights at the nearest point on the boundary of the domain instead of the convex hull,
+    /// and `GhostPoints` uses the natural neighbors inside the domain.
+    /// The sites outside the domain are excluded from the natural neighbors,
+    /// and the weights of the other sites are normalized without them.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Domain, Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    ///     Point { x: 0.0, y: 2.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// // the triangle below the diagonal from (0.0, 0.0) to (2.0, 2.0)
+    /// let domain = Domain::new(&[
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    /// ]);
+    /// let interpolator = Interpolator::new(&points).with_domain(domain);
+    ///
+    /// assert!(interpolator
+    ///     .interpolate(&values, Point { x: 1.5, y: 0.5 })
+    ///     .unwrap()
+    ///     .is_some());
+    /// assert!(interpolator
+    ///     .interpolate(&values, Point { x: 0.5, y: 1.5 })
+    ///     .unwrap()
+    ///     .is_none());
+    /// ```
+    pub fn with_domain(mut self, domain: Domain) -> Self {
+        self.in_domain = self.points.iter().map(|p| domain.contains(p)).collect();
+        self.domain = Some(domain);
+        self
+    }
+
+    /// Check if the point is inside the domain. Always true if no domain is set.
+    pub(crate) fn domain_contains(&self, p: &Point) -> bool {
+        self.domain
+            .as_ref()
+            .map_or(true, |domain| domain.contains(p))
+    }
+
+    /// The nearest point on the boundary of the domain from the point. None if no domain is set.
+    pub(crate) fn nearest_domain_point(&self, p: &Point) -> Option<Point> {
+        self.domain
+            .as_ref()
+            .map(|domain| domain.nearest_boundary_point(p))
+    }
+
+    /// Check if the site is inside the domain. Always true if no domain is set.
+    pub(crate) fn site_in_domain(&self, i: usize) -> bool {
+        self.in_domain.get(i).copied().unwrap_or(true)
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::HashSet;
+
+use crate::{
+    coordinates::laplace_weight,
+    util::{
+        circumcenter_derivative, distance_2, in_circumcircle, next_harfedge, orient, prev_harfedge,
+    },
+    voronoi::{ConvexPolygon, NO_LABEL},
+    CoordinateKind, Interpolator, InterpolatorError, Point,
+};
+
+/// Defines how the interpolator behaves for points outside the convex hull of the sites.
+///
+/// The mode is set with `Interpolator::with_extrapolation_mode`.
+/// The default is `ExtrapolationMode::None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExtrapolationMode {
+    /// Points outside the convex hull are not interpolated and `None` is returned.
+    #[default]
+    None,
+    /// The value of the nearest site is used.
+    NearestSite,
+    /// The value is linearly interpolated between the endpoints of the nearest edge of the convex hull.
+    NearestHullEdge,
+    /// Natural neighbor interpolation is extended outside the convex hull.
+    ///
+    /// The voronoi cells of the sites on the convex hull are unbounded,
+    /// so a ring of ghost points is placed around the sites to bound them.
+    /// The area stolen from the ghost points is discarded.
+    /// The ring is enlarged for the points far from the sites so that they are always inside of it.
+    /// The ghost points also bound the voronoi cells of the points near the convex hull inside the triangulation,
+    /// so that the interpolated values are continuous across the convex hull.
+    GhostPoints,
+}
+
+// The number of the ghost points placed around the sites in ExtrapolationMode::GhostPoints.
+static GHOST_POINTS: usize = 8;
+
+// The radius of the ring of the ghost points, relative to the diagonal of the bounding box of the sites.
+static GHOST_RADIUS_SCALE: f64 = 2.0;
+
+impl Interpolator {
+    /// Calculate the weights of the sites for a point outside the triangulation.
+    pub(crate) fn extrapolate(
+        &self,
+        ptarget: &Point,
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> Result<(), InterpolatorError> {
+        match self.extrapolation_mode {
+            ExtrapolationMode::None => {}
+            ExtrapolationMode::NearestSite => {
+                if let Some(i) = self.nearest_site_where(ptarget, |i| self.site_in_domain(i)) {
+                    apply_weight(i, 1., [0., 0.], 1.);
+                }
+            }
+            ExtrapolationMode::NearestHullEdge => {
+                self.apply_hull_edge_weights(ptarget, apply_weight);
+            }
+            ExtrapolationMode::GhostPoints => {
+                self.apply_ghost_point_weights(ptarget, kind, apply_weight);
+            }
+        }
+        Ok(())
+    }
+
+    /// Find the nearest site from the point.
+    /// The triangles are iterated from the nearest one until their bounding boxes get farther than the nearest site found.
+    pub(crate) fn nearest_site(&self, ptarget: &Point) -> Option<usize> {
+        self.nearest_site_where(ptarget, |_| true)
+    }
+
+    /// Find the nearest site from the point among the sites satisfying the predicate.
+    pub(crate) fn nearest_site_where(
+        &self,
+        ptarget: &Point,
+        predicate: impl Fn(usize) -> bool,
+    ) -> Option<usize> {
+        let mut nearest: Option<(usize, f64)> = None;
+        for (triangle, dist2) in self
+            .tree
+            .nearest_neighbor_iter_with_distance_2(&[ptarget.x, ptarget.y])
+        {
+            if nearest.is_some_and(|(_, d)| dist2 > d) {
+                break;
+            }
+            let it = triangle.itriangle();
+            for i in &self.triangles[it * 3..it * 3 + 3] {
+                if !predicate(*i) {
+                    continue;
+                }
+                let d = distance_2(&self.points[*i], ptarget);
+                if nearest.map_or(true, |(_, nd)| d < nd) {
+                    nearest = Some((*i, d));
+                }
+            }
+        }
+        nearest.map(|(i, _)| i)
+    }
+
+    /// Find the nearest edge on the convex hull from the point.
+    /// Returns the harfedge and the parameter (0.0-1.0) of the nearest point on the edge.
+    pub(crate) fn nearest_hull_edge(&self, ptarget: &Point) -> Option<(usize, f64)> {
+        let mut nearest: Option<(usize, f64, f64)> = None;
+        for (triangle, dist2) in self
+            .tree
+            .nearest_neighbor_iter_with_distance_2(&[ptarget.x, ptarget.y])
+        {
+            if nearest.is_some_and(|(_, _, d)| dist2 > d) {
+                break;
+            }
+            let it = triangle.itriangle();
+            for e in it * 3..it * 3 + 3 {
+                if self.harfedges[e] < self.harfedges.len() {
+                    continue;
+                }
+                let a = &self.points[self.triangles[e]];
+                let b = &self.points[self.triangles[next_harfedge(e)]];
+                let len2 = distance_2(a, b);
+                let t = if len2 > 0. {
+                    (((ptarget.x - a.x) * (b.x - a.x) + (ptarget.y - a.y) * (b.y - a.y)) / len2)
+                        .clamp(0., 1.)
+                } else {
+                    0.
+                };
+                let q = Point {
+                    x: a.x + (b.x - a.x) * t,
+                    y: a.y + (b.y - a.y) * t,
+                };
+                let d = distance_2(&q, ptarget);
+                if nearest.map_or(true, |(_, _, nd)| d < nd) {
+                    nearest = Some((e, t, d));
+                }
+            }
+        }
+        nearest.map(|(e, t, _)| (e, t))
+    }
+
+    /// The next harfedge on the convex hull, which starts from the end of the harfedge `e`.
+    pub(crate) fn next_hull_harfedge(&self, e: usize) -> usize {
+        let mut e = next_harfedge(e);
+        while self.harfedges[e] < self.harfedges.len() {
+            e = next_harfedge(self.harfedges[e]);
+        }
+        e
+    }
+
+    /// The previous harfedge on the convex hull, which ends at the start of the harfedge `e`.
+    pub(crate) fn prev_hull_harfedge(&self, e: usize) -> usize {
+        let mut e = prev_harfedge(e);
+        while self.harfedges[e] < self.harfedges.len() {
+            e = prev_harfedge(self.harfedges[e]);
+        }
+        e
+    }
+
+    // Check if the point is strictly outside of the hull edge.
+    // The triangles are clockwise, so the outside of the edge is on the left side.
+    pub(crate) fn hull_edge_visible(&self, e: usize, ptarget: &Point) -> bool {
+        let a = &self.points[self.triangles[e]];
+        let b = &self.points[self.triangles[next_harfedge(e)]];
+        orient(a, b, ptarget) > 0.
+    }
+
+    /// Find the harfedges on the convex hull which are visible from the point outside the triangulation.
+    pub(crate) fn visible_hull_edges(&self, ptarget: &Point) -> Vec<usize> {
+        let edge = if let Some((e, _)) = self.nearest_hull_edge(ptarget) {
+            e
+        } else {
+            return Vec::new();
+        };
+
+        let mut visible = Vec::new();
+        if self.hull_edge_visible(edge, ptarget) {
+            visible.push(edge);
+        }
+        let mut e = self.next_hull_harfedge(edge);
+        while e != edge && self.hull_edge_visible(e, ptarget) {
+            visible.push(e);
+            e = self.next_hull_harfedge(e);
+        }
+        let mut e = self.prev_hull_harfedge(edge);
+        while e != edge && !visible.contains(&e) && self.hull_edge_visible(e, ptarget) {
+            visible.push(e);
+            e = self.prev_hull_harfedge(e);
+        }
+        visible
+    }
+
+    /// Find the triangles whose circumcircle contains the point (the boyer-watson cavity),
+    /// by searching the neighboring triangles from the seeds.
+    /// The seeds whose circumcircle does not contain the point are ignored.
+    pub(crate) fn find_cavity(&self, ptarget: &Point, seeds: Vec<usize>) -> Vec<usize> {
+        let mut cavity = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = seeds;
+        while let Some(it) = stack.pop() {
+            if !visited.insert(it) {
+                continue;
+            }
+            let triangle_points = [
+                &self.points[self.triangles[it * 3]],
+                &self.points[self.triangles[it * 3 + 1]],
+                &self.points[self.triangles[it * 3 + 2]],
+            ];
+            if !in_circumcircle(&triangle_points, ptarget) {
+                continue;
+            }
+            cavity.push(it);
+            for e in it * 3..it * 3 + 3 {
+                let opposite = self.harfedges[e];
+                // the cavity does not spread across the breaklines
+                if opposite < self.harfedges.len() && !self.is_breakline(e) {
+                    stack.push(opposite / 3);
+                }
+            }
+        }
+        cavity
+    }
+
+    fn apply_hull_edge_weights(
+        &self,
+        ptarget: &Point,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) {
+        if let Some((e, t)) = self.nearest_hull_edge(ptarget) {
+            let (a, b) = (self.triangles[e], self.triangles[next_harfedge(e)]);
+            self.apply_edge_weights(a, b, t, apply_weight);
+        }
+    }
+
+    /// Apply the weights linearly interpolated between the sites `a` and `b`
+    /// at the parameter `t` (0.0-1.0) of the point on the edge.
+    pub(crate) fn apply_edge_weights(
+        &self,
+        a: usize,
+        b: usize,
+        t: f64,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) {
+        // the derivative of the parameter of the point on the edge
+        let (pa, pb) = (&self.points[a], &self.points[b]);
+        let len2 = distance_2(pa, pb);
+        let dt = if t > 0. && t < 1. {
+            [(pb.x - pa.x) / len2, (pb.y - pa.y) / len2]
+        } else {
+            [0., 0.]
+        };
+
+        if t < 1. {
+            apply_weight(a, 1. - t, [-dt[0], -dt[1]], 1. - t);
+        }
+        if t > 0. {
+            apply_weight(b, t, dt, 1.);
+        }
+    }
+
+    fn apply_ghost_point_weights(
+        &self,
+        ptarget: &Point,
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) {
+        let visible = self.visible_hull_edges(ptarget);
+        if visible.is_empty() {
+            self.apply_hull_edge_weights(ptarget, apply_weight);
+            return;
+        }
+
+        // The natural neighbors are the vertices of the visible edges
+        // and the vertices of the triangles whose circumcircle contains the point.
+        let mut neighbors = Vec::new();
+        for e in &visible {
+            add_neighbor(&mut neighbors, self.triangles[*e]);
+            add_neighbor(&mut neighbors, self.triangles[next_harfedge(*e)]);
+        }
+        for it in self.find_cavity(ptarget, visible.iter().map(|e| e / 3).collect()) {
+            for e in it * 3..it * 3 + 3 {
+                add_neighbor(&mut neighbors, self.triangles[e]);
+            }
+        }
+
+        self.apply_ghost_voronoi_weights(ptarget, &neighbors, kind, apply_weight);
+    }
+
+    /// Apply the weights bounded by the ghost points to the point inside the triangulation, in the triangle `it`.
+    ///
+    /// The voronoi cell of the point near the convex hull is bounded by the ghost points as well as outside the convex hull,
+    /// so that the weights are continuous across the convex hull.
+    /// Returns false without applying the weights if the ghost points do not affect the voronoi cell of the point,
+    /// where the weights are the same as the ones given by the boyer-watson envelope.
+    pub(crate) fn apply_ghost_point_weights_inside(
+        &self,
+        ptarget: &Point,
+        it: usize,
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> bool {
+        let cavity = self.find_cavity(ptarget, vec![it]);
+        let (_, _, ghosts) = self.ghost_points(ptarget);
+
+        // The vertices of the voronoi cell of the point are the circumcenters of the new triangles
+        // made of the point and the edges on the boundary of the cavity.
+        // The ghost points take the area from the cell only if they are in the circumcircles.
+        let reaches_ghost = cavity.iter().any(|it| {
+            (it * 3..it * 3 + 3).any(|e| {
+                let opposite = self.harfedges[e];
+                if opposite < self.harfedges.len()
+                    && !self.is_breakline(e)
+                    && cavity.contains(&(opposite / 3))
+                {
+                    return false;
+                }
+                let triangle = [
+                    &self.points[self.triangles[e]],
+                    &self.points[self.triangles[next_harfedge(e)]],
+                    ptarget,
+                ];
+                // the circumcircle of the point on the convex hull is unbounded
+                orient(triangle[0], triangle[1], triangle[2]) == 0.
+                    || ghosts.iter().any(|g| in_circumcircle(&triangle, g))
+            })
+        });
+        if !reaches_ghost {
+            return false;
+        }
+
+        let mut neighbors = Vec::new();
+        for it in cavity {
+            for e in it * 3..it * 3 + 3 {
+                add_neighbor(&mut neighbors, self.triangles[e]);
+            }
+        }
+        self.apply_ghost_voronoi_weights(ptarget, &neighbors, kind, apply_weight);
+        true
+    }
+
+    /// The ghost points on the ring around the sites.
+    /// Returns the center and the radius of the ring, and the ghost points.
+    fn ghost_points(&self, ptarget: &Point) -> (Point, f64, Vec<Point>) {
+        let envelope = self.tree.root().envelope();
+        let (lower, upper) = (envelope.lower(), envelope.upper());
+        let center = Point {
+            x: (lower[0] + upper[0]) / 2.,
+            y: (lower[1] + upper[1]) / 2.,
+        };
+        let diagonal = ((upper[0] - lower[0]).powi(2) + (upper[1] - lower[1]).powi(2)).sqrt();
+        let radius = f64::max(
+            diagonal * GHOST_RADIUS_SCALE,
+            distance_2(&center, ptarget).sqrt() * 2.,
+        );
+        let ghosts = (0..GHOST_POINTS)
+            .map(|k| {
+                let angle = std::f64::consts::TAU * k as f64 / GHOST_POINTS as f64;
+                Point {
+                    x: center.x + radius * angle.cos(),
+                    y: center.y + radius * angle.sin(),
+                }
+            })
+            .collect::<Vec<_>>();
+        (center, radius, ghosts)
+    }
+
+    /// Apply the weights given by the voronoi cell of the point among the neighbors and the ghost points.
+    /// The neighbors must include all the natural neighbors of the point.
+    fn apply_ghost_voronoi_weights(
+        &self,
+        ptarget: &Point,
+        neighbors: &[usize],
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) {
+        let (center, radius, ghosts) = self.ghost_points(ptarget);
+
+        // The voronoi cell of the point after the insertion.
+        // The edges of the cell are labeled with the index of the site in `sites`.
+        let sites = neighbors
+            .iter()
+            .map(|i| &self.points[*i])
+            .chain(ghosts.iter())
+            .collect::<Vec<_>>();
+        let mut cell = ConvexPolygon::square(&center, radius * 4.);
+        for (k, site) in sites.iter().enumerate() {
+            cell.clip_bisector(ptarget, site, k);
+        }
+
+        let mut tmp_weight_sum = 0.;
+        for (k, i) in neighbors.iter().enumerate() {
+            let site = sites[k];
+            let (weight, gradient) = match kind {
+                CoordinateKind::Sibson => {
+                    // The area stolen from the site.
+                    let mut stolen = cell.clone();
+                    for (l, other) in sites.iter().enumerate() {
+                        if k != l {
+                            stolen.clip_bisector(site, other, NO_LABEL);
+                        }
+                    }
+                    // The derivative of the stolen area is given by the voronoi edge between the point and the site.
+                    let gradient = if let Some((start, end, _, _)) = cell.edge(k) {
+                        let scale =
+                            distance_2(&start, &end).sqrt() / distance_2(site, ptarget).sqrt();
+                        [
+                            scale * ((start.x + end.x) / 2. - ptarget.x),
+                            scale * ((start.y + end.y) / 2. - ptarget.y),
+                        ]
+                    } else {
+                        [0., 0.]
+                    };
+                    (stolen.area(), gradient)
+                }
+                CoordinateKind::Laplace => {
+                    if let Some((start, end, lprev, lnext)) = cell.edge(k) {
+                        // The endpoints on the bounding square do not move with the point.
+                        let derivative = |l: usize| {
+                            if l < sites.len() {
+                                circumcenter_derivative(ptarget, site, sites[l])
+                            } else {
+                                [0., 0.]
+                            }
+                        };
+                        laplace_weight(
+                            ptarget,
+                            site,
+                            &start,
+                            &end,
+                            derivative(lprev),
+                            derivative(lnext),
+                        )
+                    } else {
+                        (0., [0., 0.])
+                    }
+                }
+            };
+            if weight > 0. {
+                tmp_weight_sum += weight;
+                apply_weight(*i, weight, gradient, tmp_weight_sum);
+            }
+        }
+    }
+}
+
+fn add_neighbor(neighbors: &mut Vec<usize>, i: usize) {
+    if !neighbors.contains(&i) {
+        neighbors.push(i);
+    }
+}
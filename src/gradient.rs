@@ -0,0 +1,228 @@
+use crate::{
+    util::{next_harfedge, prev_harfedge},
+    Interpolator, InterpolatorError, Point,
+};
+
+/// Defines which sites are used to estimate the gradient of a site.
+///
+/// Used by `Interpolator::estimate_gradients`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GradientEstimation {
+    /// Only the Delaunay neighbors of the site are used.
+    #[default]
+    FirstRing,
+    /// The neighbors of the neighbors are also used for the sites on the convex hull,
+    /// whose Delaunay neighbors lie only on one side of them.
+    SecondRingOnHull,
+}
+
+impl Interpolator {
+    /// Find an outgoing harfedge for each site.
+    /// The harfedge on the convex hull is preferred so that all the neighbors are iterated from it.
+    /// `harfedges.len()` is set for the sites which are not in the triangulation.
+    pub(crate) fn outgoing_harfedges(&self) -> Vec<usize> {
+        let mut outgoing = vec![self.harfedges.len(); self.points.len()];
+        for (e, i) in self.triangles.iter().enumerate() {
+            if outgoing[*i] >= self.harfedges.len() || self.harfedges[e] >= self.harfedges.len() {
+                outgoing[*i] = e;
+            }
+        }
+        outgoing
+    }
+
+    /// Iterate the Delaunay neighbors of the site, starting from the outgoing harfedge `start`.
+    /// Returns whether the site is on the convex hull.
+    pub(crate) fn for_each_neighbor(&self, start: usize, mut f: impl FnMut(usize)) -> bool {
+        let mut e = start;
+        loop {
+            f(self.triangles[next_harfedge(e)]);
+            let incoming = prev_harfedge(e);
+            let opposite = self.harfedges[incoming];
+            if opposite >= self.harfedges.len() {
+                // reached the convex hull
+                f(self.triangles[incoming]);
+                return true;
+            }
+            e = opposite;
+            if e == start {
+                return false;
+            }
+        }
+    }
+
+    /// Estimate the gradient (df/dx, df/dy) at every site.
+    ///
+    /// A plane is fitted to the values of the Delaunay neighbors of each site by the least squares method,
+    /// weighted by the inverse squared distance from the site.
+    /// The result can be used as the gradients of `interpolate_c1`.
+    ///
+    /// The sites merged into another site (see `new_with_deduplication`) take the gradient of that site.
+    /// `[0.0, 0.0]` is returned for the sites whose gradient cannot be determined,
+    /// such as the removed sites.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{GradientEstimation, Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    ///     Point { x: 0.4, y: 0.6 },
+    /// ];
+    ///
+    /// // f(x, y) = 2x - y
+    /// let values = points.iter().map(|p| 2.0 * p.x - p.y).collect::<Vec<_>>();
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let gradients = interpolator
+    ///     .estimate_gradients(&values, GradientEstimation::FirstRing)
+    ///     .unwrap();
+    ///
+    /// for g in gradients {
+    ///     assert!((g[0] - 2.0).abs() < 1e-6);
+    ///     assert!((g[1] + 1.0).abs() < 1e-6);
+    /// }
+    /// ```
+    pub fn estimate_gradients(
+        &self,
+        values: &[f64],
+        estimation: GradientEstimation,
+    ) -> Result<Vec<[f64; 2]>, InterpolatorError> {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let outgoing = self.outgoing_harfedges();
+        let mut neighbors = Vec::new();
+
+        let mut gradients = (0..self.site_count())
+            .map(|i| {
+                if outgoing[i] >= self.harfedges.len() {
+                    return [0., 0.];
+                }
+
+                neighbors.clear();
+                let on_hull = self.for_each_neighbor(outgoing[i], |j| neighbors.push(j));
+
+                if on_hull && estimation == GradientEstimation::SecondRingOnHull {
+                    for k in 0..neighbors.len() {
+                        self.for_each_neighbor(outgoing[neighbors[k]], |j| {
+                            if j != i && !neighbors.contains(&j) {
+                                neighbors.push(j);
+                            }
+                        });
+                    }
+                }
+
+                // normal equations of the weighted least squares
+                let (mut axx, mut axy, mut ayy, mut bx, mut by) = (0., 0., 0., 0., 0.);
+                let pi = &self.points[i];
+                for j in &neighbors {
+                    let pj = &self.points[*j];
+                    let (dx, dy, dz) = (
+                        pj.x - pi.x,
+                        pj.y - pi.y,
+                        values[self.site_of(*j)] - values[i],
+                    );
+                    let d2 = dx * dx + dy * dy;
+                    if d2 == 0. {
+                        continue;
+                    }
+                    let w = 1. / d2;
+                    axx += w * dx * dx;
+                    axy += w * dx * dy;
+                    ayy += w * dy * dy;
+                    bx += w * dx * dz;
+                    by += w * dy * dz;
+                }
+
+                let det = axx * ayy - axy * axy;
+                if det.abs() <= f64::EPSILON * (axx + ayy).powi(2) {
+                    return [0., 0.];
+                }
+                [(ayy * bx - axy * by) / det, (axx * by - axy * bx) / det]
+            })
+            .collect::<Vec<_>>();
+
+        for i in 0..gradients.len() {
+            if !self.vacant[i] && self.merged[i] != i {
+                gradients[i] = gradients[self.merged[i]];
+            }
+        }
+        Ok(gradients)
+    }
+
+    /// Interpolate the value at the point together with its gradient (df/dx, df/dy).
+    ///
+    /// The gradient is the exact derivative of the interpolated surface,
+    /// which is calculated from the derivatives of the natural neighbor coordinates on the boyer-watson envelope.
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
+    ///
+    /// The surface is not differentiable at the sites, so the gradient at a site is `[0.0, 0.0]`.
+    /// Use `estimate_gradients` for the gradients at the sites.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    ///     Point { x: 0.4, y: 0.6 },
+    /// ];
+    ///
+    /// // f(x, y) = 2x - y
+    /// let values = points.iter().map(|p| 2.0 * p.x - p.y).collect::<Vec<_>>();
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let (value, gradient) = interpolator
+    ///     .interpolate_with_gradient(&values, Point { x: 0.3, y: 0.2 })
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert!((value - 0.4).abs() < 1e-6);
+    /// assert!((gradient[0] - 2.0).abs() < 1e-6);
+    /// assert!((gradient[1] + 1.0).abs() < 1e-6);
+    /// ```
+    pub fn interpolate_with_gradient<P>(
+        &self,
+        values: &[f64],
+        ptarget: P,
+    ) -> Result<Option<(f64, [f64; 2])>, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        // f = sum(z_i * w_i) / sum(w_i)
+        let mut weight_sum = 0.;
+        let mut weight_sum_gradient = [0., 0.];
+        let mut value_sum = 0.;
+        let mut value_sum_gradient = [0., 0.];
+        self.perform_interpoation::<P>(ptarget, &mut |i, weight, gradient, _| {
+            weight_sum += weight;
+            value_sum += values[i] * weight;
+            for k in 0..2 {
+                weight_sum_gradient[k] += gradient[k];
+                value_sum_gradient[k] += values[i] * gradient[k];
+            }
+        })?;
+
+        if weight_sum == 0. {
+            return Ok(None);
+        }
+
+        let value = value_sum / weight_sum;
+        let gradient = [
+            (value_sum_gradient[0] - value * weight_sum_gradient[0]) / weight_sum,
+            (value_sum_gradient[1] - value * weight_sum_gradient[1]) / weight_sum,
+        ];
+        Ok(Some((value, gradient)))
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{Interpolator, InterpolatorError, Lerpable, Point, QueryHint};
+
+/// Defines a regular grid of points to be interpolated.
+///
+/// The point at the column `ix` and the row `iy` is `(origin.x + ix * dx, origin.y + iy * dy)`.
+#[derive(Debug, Clone)]
+pub struct GridSpec {
+    /// The point at the column 0 and the row 0.
+    pub origin: Point,
+    /// The interval between the columns.
+    pub dx: f64,
+    /// The interval between the rows.
+    pub dy: f64,
+    /// The number of the columns.
+    pub nx: usize,
+    /// The number of the rows.
+    pub ny: usize,
+}
+
+impl GridSpec {
+    /// The point at the column `ix` and the row `iy`.
+    pub fn point(&self, ix: usize, iy: usize) -> Point {
+        Point {
+            x: self.origin.x + ix as f64 * self.dx,
+            y: self.origin.y + iy as f64 * self.dy,
+        }
+    }
+}
+
+impl Interpolator {
+    /// Interpolate the values at all the points of the grid.
+    ///
+    /// The result is in row-major order: the value at the column `ix` and the row `iy` is at `iy * nx + ix`.
+    /// The grid is scanned row by row, and the triangle containing each point is found by walking from the triangle of the previous point
+    /// (see `interpolate_from_hint`), so it is much faster than calling `interpolate` for each point.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{GridSpec, Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let grid = GridSpec {
+    ///     origin: Point { x: 0.25, y: 0.25 },
+    ///     dx: 0.5,
+    ///     dy: 0.5,
+    ///     nx: 3,
+    ///     ny: 2,
+    /// };
+    ///
+    /// let result = interpolator.interpolate_grid(&values, grid).unwrap();
+    /// assert_eq!(result.len(), 6);
+    /// assert!((result[0].unwrap() - 0.5).abs() < 1e-6);
+    /// assert!((result[4].unwrap() - 1.5).abs() < 1e-6);
+    ///
+    /// // the last column is outside the convex hull
+    /// assert!(result[2].is_none());
+    /// ```
+    pub fn interpolate_grid<V>(
+        &self,
+        values: &[V],
+        grid: GridSpec,
+    ) -> Result<Vec<Option<V>>, InterpolatorError>
+    where
+        V: Lerpable,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let mut result = Vec::with_capacity(grid.nx * grid.ny);
+        for iy in 0..grid.ny {
+            result.extend(self.interpolate_grid_row(values, &grid, iy)?);
+        }
+        Ok(result)
+    }
+
+    /// Interpolate the values at the points of the row `iy` of the grid.
+    pub(crate) fn interpolate_grid_row<V>(
+        &self,
+        values: &[V],
+        grid: &GridSpec,
+        iy: usize,
+    ) -> Result<Vec<Option<V>>, InterpolatorError>
+    where
+        V: Lerpable,
+    {
+        let mut hint = QueryHint::default();
+        (0..grid.nx)
+            .map(|ix| {
+                self.interpolate_with_hint(values, grid.point(ix, iy), &mut hint)
+                    .map(|(value, _)| value)
+            })
+            .collect()
+    }
+}
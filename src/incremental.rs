@@ -0,0 +1,522 @@
+use std::collections::HashMap;
+
+use delaunator::EMPTY;
+
+use crate::{
+    breakline::breakline_key,
+    primitives::Triangle,
+    util::{distance_2, in_circumcircle, next_harfedge, orient},
+    Interpolator, InterpolatorError, Point,
+};
+
+impl Interpolator {
+    /// Triangulate all the sites (except the removed and the merged ones) again and rebuild the RTree.
+    pub(crate) fn retriangulate(&mut self) {
+        let active = (0..self.points.len())
+            .filter(|i| !self.vacant[*i] && self.merged[*i] == *i)
+            .collect::<Vec<_>>();
+        let points = active
+            .iter()
+            .map(|i| self.points[*i].clone())
+            .collect::<Vec<_>>();
+        let triangulation = delaunator::triangulate(&points);
+        self.triangles = triangulation.triangles.iter().map(|i| active[*i]).collect();
+        self.harfedges = triangulation.halfedges;
+        self.rebuild_tree();
+        self.restore_breaklines();
+    }
+
+    pub(crate) fn rebuild_tree(&mut self) {
+        let triangles = (0..self.triangles.len() / 3)
+            .map(|t| Triangle::from_triangle(&self.points, &self.triangles, t))
+            .collect::<Vec<_>>();
+        self.tree = rstar::RTree::bulk_load(triangles);
+    }
+
+    /// Insert a new site into the interpolator and return its index.
+    ///
+    /// The triangulation is updated locally by retriangulating the boyer-watson cavity of the new site,
+    /// so the indices of the existing sites are kept and the new site is appended to the end.
+    /// The value of the new site must be appended to the end of the values passed to `interpolate`.
+    ///
+    /// A site within the merge tolerance (see `new_with_deduplication`) of an existing site
+    /// is merged into it by the merge policy, but it still takes an index.
+    /// `MergePolicy::Error` is treated as `MergePolicy::KeepFirst` here.
+    ///
+    /// In the periodic mode (see `new_periodic`), the site is wrapped into the domain of one period,
+    /// and the whole triangulation is rebuilt with the ghost copies.
+    ///
+    /// Returns `InterpolatorError::NonFiniteCoordinate` with the index the site would take
+    /// if the point has a NaN or infinite coordinate, leaving the interpolator unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let mut values = vec![0.0f64, 1.0, 1.0];
+    ///
+    /// let mut interpolator = Interpolator::new(&points);
+    ///
+    /// let index = interpolator.insert(Point { x: 1.0, y: 1.0 }).unwrap();
+    /// assert_eq!(index, 3);
+    /// values.push(2.0);
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 0.75, y: 0.75 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 1.5).abs() < 1e-6);
+    /// ```
+    pub fn insert<P>(&mut self, point: P) -> Result<usize, InterpolatorError>
+    where
+        P: Into<Point>,
+    {
+        let point = point.into();
+        if !point.x.is_finite() || !point.y.is_finite() {
+            return Err(InterpolatorError::NonFiniteCoordinate(self.site_count()));
+        }
+        if self.is_periodic() {
+            return Ok(self.insert_periodic(point));
+        }
+        let index = self.points.len();
+        self.points.push(point.clone());
+        self.vacant.push(false);
+        self.merged.push(index);
+        if let Some(domain) = &self.domain {
+            self.in_domain.push(domain.contains(&point));
+        }
+
+        if let Some(site) = self
+            .nearest_site(&point)
+            .filter(|site| distance_2(&self.points[*site], &point) <= self.merge_tolerance.powi(2))
+        {
+            self.merge_into(index, site);
+            return Ok(index);
+        }
+
+        self.insert_site(index);
+        Ok(index)
+    }
+
+    /// Insert the site with the index into the triangulation.
+    fn insert_site(&mut self, index: usize) {
+        let point = self.points[index].clone();
+
+        if self.triangles.is_empty() {
+            // The sites were collinear (or too few) so far.
+            self.retriangulate();
+            return;
+        }
+
+        // The triangles to be removed (cavity),
+        // and the edges on the boundary of the region to be retriangulated.
+        // Each boundary edge is (start, end, opposite harfedge) and forms a new triangle (start, end, point).
+        let (cavity, boundary) = if let Some(it) = self.fit_in_triangle(&point) {
+            let triangle = &self.triangles[it * 3..it * 3 + 3];
+            if triangle
+                .iter()
+                .any(|i| self.points[*i].x == point.x && self.points[*i].y == point.y)
+            {
+                // duplicated site
+                return;
+            }
+
+            // the breakline under the new site is split at it
+            if let Some((a, b)) = self.split_breakline(&point, it) {
+                let cavity = self.find_cavity(&point, vec![it]);
+                let boundary = self.cavity_boundary(&point, &cavity, &[]);
+                self.fill_region(index, &cavity, &boundary);
+                self.breaklines.insert(breakline_key(a, index));
+                self.breaklines.insert(breakline_key(b, index));
+                return;
+            }
+
+            let cavity = self.find_cavity(&point, vec![it]);
+            let boundary = self.cavity_boundary(&point, &cavity, &[]);
+            (cavity, boundary)
+        } else {
+            let visible = self.visible_hull_edges(&point);
+            if visible.is_empty() {
+                return;
+            }
+            let cavity = self.find_cavity(&point, visible.iter().map(|e| e / 3).collect());
+            let boundary = self.cavity_boundary(&point, &cavity, &visible);
+            (cavity, boundary)
+        };
+
+        self.fill_region(index, &cavity, &boundary);
+    }
+
+    /// Remove the site from the interpolator.
+    ///
+    /// The triangles around the site are retriangulated locally,
+    /// and the index of the site is marked as vacant instead of being reused,
+    /// so the indices of the other sites are kept.
+    /// The values passed to `interpolate` must still have an element (which is ignored) for the removed site.
+    ///
+    /// In the periodic mode (see `new_periodic`), the whole triangulation is rebuilt with the ghost copies.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    ///     Point { x: 0.5, y: 0.5 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0, 100.0];
+    ///
+    /// let mut interpolator = Interpolator::new(&points);
+    /// interpolator.remove(4).unwrap();
+    /// assert!(interpolator.is_vacant(4));
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 0.5, y: 0.5 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Result<(), InterpolatorError> {
+        if self.is_periodic() {
+            return self.remove_periodic(index);
+        }
+        if self.is_vacant(index) {
+            return Err(InterpolatorError::InvalidSiteIndex(index));
+        }
+        self.vacant[index] = true;
+
+        let site = self.merged[index];
+        if site != index {
+            // the site is merged into another site
+            if let Some(group) = self.groups.get_mut(&site) {
+                group.retain(|i| *i != index);
+            }
+            return Ok(());
+        }
+
+        // The next site merged into the removed site takes its place.
+        let successor = self.groups.remove(&index).and_then(|mut group| {
+            group.retain(|i| *i != index);
+            let successor = *group.first()?;
+            for i in &group {
+                self.merged[*i] = successor;
+            }
+            if group.len() > 1 {
+                self.groups.insert(successor, group);
+            }
+            Some(successor)
+        });
+
+        let point = self.points[index].clone();
+        let star = self
+            .tree
+            .locate_all_at_point(&[point.x, point.y])
+            .map(|t| t.itriangle())
+            .filter(|it| self.triangles[it * 3..it * 3 + 3].contains(&index))
+            .collect::<Vec<_>>();
+
+        if let Some(successor) = successor {
+            let p = &self.points[successor];
+            if p.x == point.x && p.y == point.y {
+                // the triangulation is not changed
+                self.rename_breaklines(index, successor);
+                for it in star {
+                    for i in &mut self.triangles[it * 3..it * 3 + 3] {
+                        if *i == index {
+                            *i = successor;
+                        }
+                    }
+                }
+                return Ok(());
+            }
+        }
+
+        let rejoined = self.detach_breaklines(index);
+        if !star.is_empty() {
+            self.remove_site(index, &star);
+        }
+        if let Some((a, b)) = rejoined {
+            // the breaklines have been checked not to cross each other
+            let _ = self.insert_breakline(a, b);
+        }
+        if let Some(successor) = successor {
+            self.insert_site(successor);
+        }
+        Ok(())
+    }
+
+    /// Remove the site with the index from the triangulation.
+    /// `star` is the triangles around the site.
+    fn remove_site(&mut self, index: usize, star: &[usize]) {
+        // The edges of the star opposite to the removed site (the link), as start -> (end, opposite harfedge).
+        let mut link = HashMap::new();
+        for it in star {
+            let k = (0..3)
+                .find(|k| self.triangles[it * 3 + k] == index)
+                .unwrap();
+            let e = next_harfedge(it * 3 + k);
+            link.insert(
+                self.triangles[e],
+                (self.triangles[next_harfedge(e)], self.harfedges[e]),
+            );
+        }
+
+        // Sort the vertices of the link.
+        // If the site is on the convex hull, the link is an open chain starting from the vertex with no incoming edge.
+        let ends = link.values().map(|(end, _)| *end).collect::<Vec<_>>();
+        let open_start = link.keys().find(|v| !ends.contains(v)).copied();
+        let closed = open_start.is_none();
+        let chain_start = open_start.unwrap_or(ends[0]);
+        let mut chain = vec![chain_start];
+        loop {
+            match link.get(chain.last().unwrap()) {
+                Some((next, _)) if *next != chain_start && chain.len() <= link.len() => {
+                    chain.push(*next)
+                }
+                _ => break,
+            }
+        }
+
+        let triangles = if let Some(triangles) = self.triangulate_link(chain, closed) {
+            triangles
+        } else {
+            // The local retriangulation failed (this should not happen for a valid Delaunay triangulation).
+            self.retriangulate();
+            return;
+        };
+
+        self.replace_star(star, &triangles, &link);
+    }
+
+    /// Check if the site has been removed (or does not exist).
+    pub fn is_vacant(&self, index: usize) -> bool {
+        self.vacant.get(index).copied().unwrap_or(true)
+    }
+
+    /// Triangulate the polygon (or the pocket between the open chain and its convex hull)
+    /// made by the link of the removed site, by clipping the ears whose circumcircle is empty.
+    fn triangulate_link(&self, mut chain: Vec<usize>, closed: bool) -> Option<Vec<[usize; 3]>> {
+        let mut triangles = Vec::new();
+        while chain.len() >= 3 {
+            let n = chain.len();
+            if closed && n == 3 {
+                triangles.push([chain[0], chain[1], chain[2]]);
+                break;
+            }
+
+            let mut convex = false;
+            let mut found = None;
+            let range = if closed { 0..n } else { 1..n - 1 };
+            for i in range {
+                let ear = [chain[(i + n - 1) % n], chain[i], chain[(i + 1) % n]];
+                let ear_points = ear.map(|v| &self.points[v]);
+                // the triangles are clockwise
+                if orient(ear_points[0], ear_points[1], ear_points[2]) >= 0. {
+                    continue;
+                }
+                convex = true;
+
+                // The ear is a Delaunay triangle if no other vertex is inside its circumcircle.
+                if !chain
+                    .iter()
+                    .any(|v| !ear.contains(v) && in_circumcircle(&ear_points, &self.points[*v]))
+                {
+                    found = Some(ear);
+                    chain.remove(i);
+                    break;
+                }
+            }
+
+            if let Some(ear) = found {
+                triangles.push(ear);
+            } else if convex {
+                return None;
+            } else {
+                // the rest of the open chain is on the convex hull
+                break;
+            }
+        }
+        Some(triangles)
+    }
+
+    /// Replace the triangles of the star with the new triangles,
+    /// and move the last triangles into the slots left over.
+    pub(crate) fn replace_star(
+        &mut self,
+        star: &[usize],
+        triangles: &[[usize; 3]],
+        link: &HashMap<usize, (usize, usize)>,
+    ) {
+        for it in star {
+            self.tree
+                .remove(&Triangle::from_triangle(&self.points, &self.triangles, *it));
+        }
+
+        let mut internal = HashMap::new();
+        for (triangle, it) in triangles.iter().zip(star.iter()) {
+            for k in 0..3 {
+                let e = it * 3 + k;
+                let (start, end) = (triangle[k], triangle[(k + 1) % 3]);
+                self.triangles[e] = start;
+                self.harfedges[e] = EMPTY;
+                match link.get(&start) {
+                    Some((link_end, opposite)) if *link_end == end => {
+                        self.harfedges[e] = *opposite;
+                        if *opposite != EMPTY {
+                            self.harfedges[*opposite] = e;
+                        }
+                    }
+                    _ => {
+                        if let Some(o) = internal.remove(&(end, start)) {
+                            self.harfedges[e] = o;
+                            self.harfedges[o] = e;
+                        } else {
+                            internal.insert((start, end), e);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the edges of the link which are not used become the convex hull
+        for (start, (end, opposite)) in link {
+            if *opposite != EMPTY
+                && !triangles
+                    .iter()
+                    .any(|t| (0..3).any(|k| t[k] == *start && t[(k + 1) % 3] == *end))
+            {
+                self.harfedges[*opposite] = EMPTY;
+            }
+        }
+
+        for it in &star[..triangles.len()] {
+            self.tree
+                .insert(Triangle::from_triangle(&self.points, &self.triangles, *it));
+        }
+
+        let mut left_over = star[triangles.len()..].to_vec();
+        left_over.sort_unstable_by(|a, b| b.cmp(a));
+        for it in left_over {
+            let last = self.triangles.len() / 3 - 1;
+            if it != last {
+                self.tree.remove(&Triangle::from_triangle(
+                    &self.points,
+                    &self.triangles,
+                    last,
+                ));
+                for k in 0..3 {
+                    let (from, to) = (last * 3 + k, it * 3 + k);
+                    self.triangles[to] = self.triangles[from];
+                    self.harfedges[to] = self.harfedges[from];
+                    let opposite = self.harfedges[to];
+                    if opposite != EMPTY {
+                        self.harfedges[opposite] = to;
+                    }
+                }
+                self.tree
+                    .insert(Triangle::from_triangle(&self.points, &self.triangles, it));
+            }
+            self.triangles.truncate(last * 3);
+            self.harfedges.truncate(last * 3);
+        }
+    }
+
+    /// Find the boundary edges of the region made of the cavity and the outside of the visible hull edges.
+    fn cavity_boundary(
+        &self,
+        ptarget: &Point,
+        cavity: &[usize],
+        visible: &[usize],
+    ) -> Vec<(usize, usize, usize)> {
+        let mut boundary = Vec::new();
+        for it in cavity {
+            for e in it * 3..it * 3 + 3 {
+                let opposite = self.harfedges[e];
+                let inside = if opposite < self.harfedges.len() {
+                    cavity.contains(&(opposite / 3))
+                } else {
+                    // the hull edge is also removed if the new site is on it
+                    let a = &self.points[self.triangles[e]];
+                    let b = &self.points[self.triangles[next_harfedge(e)]];
+                    orient(a, b, ptarget) >= 0.
+                };
+                if !inside {
+                    boundary.push((
+                        self.triangles[e],
+                        self.triangles[next_harfedge(e)],
+                        opposite,
+                    ));
+                }
+            }
+        }
+        for e in visible {
+            if !cavity.contains(&(e / 3)) {
+                // the new triangle is outside of the visible edge
+                boundary.push((self.triangles[next_harfedge(*e)], self.triangles[*e], *e));
+            }
+        }
+        boundary
+    }
+
+    /// Replace the triangles in `region` with the new triangles connecting `boundary` to the site `index`.
+    /// The slots of the removed triangles are reused, and the rest of the new triangles are appended.
+    fn fill_region(&mut self, index: usize, region: &[usize], boundary: &[(usize, usize, usize)]) {
+        for it in region {
+            self.tree
+                .remove(&Triangle::from_triangle(&self.points, &self.triangles, *it));
+        }
+
+        let mut slots = region.to_vec();
+        while slots.len() < boundary.len() {
+            slots.push(self.triangles.len() / 3);
+            self.triangles.extend([0, 0, 0]);
+            self.harfedges.extend([EMPTY, EMPTY, EMPTY]);
+        }
+
+        // harfedges (end -> new site) and (new site -> start) of the new triangles, keyed by the other vertex
+        let mut incoming = HashMap::new();
+        let mut outgoing = HashMap::new();
+
+        for ((start, end, opposite), it) in boundary.iter().zip(slots.iter()) {
+            let t = it * 3;
+            self.triangles[t] = *start;
+            self.triangles[t + 1] = *end;
+            self.triangles[t + 2] = index;
+            if *opposite < self.harfedges.len() {
+                self.harfedges[t] = *opposite;
+                self.harfedges[*opposite] = t;
+            } else {
+                self.harfedges[t] = EMPTY;
+            }
+            incoming.insert(*end, t + 1);
+            outgoing.insert(*start, t + 2);
+        }
+
+        for (vertex, e) in &incoming {
+            if let Some(o) = outgoing.get(vertex) {
+                self.harfedges[*e] = *o;
+                self.harfedges[*o] = *e;
+            } else {
+                self.harfedges[*e] = EMPTY;
+            }
+        }
+        for (vertex, o) in &outgoing {
+            if !incoming.contains_key(vertex) {
+                self.harfedges[*o] = EMPTY;
+            }
+        }
+
+        for it in slots {
+            self.tree
+                .insert(Triangle::from_triangle(&self.points, &self.triangles, it));
+        }
+    }
+}
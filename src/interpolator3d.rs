@@ -0,0 +1,525 @@
+use std::collections::{HashMap, HashSet};
+
+use delaunator::EMPTY;
+
+use crate::{
+    polyhedron::ConvexPolyhedron,
+    primitives::Tetrahedron,
+    util::{circumcenter3d, in_circumsphere, orient3d},
+    InterpolatorError, Lerpable,
+};
+
+/// Represents a 3D point.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Point3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl From<[f64; 3]> for Point3D {
+    fn from(p: [f64; 3]) -> Self {
+        Self {
+            x: p[0],
+            y: p[1],
+            z: p[2],
+        }
+    }
+}
+
+impl From<(f64, f64, f64)> for Point3D {
+    fn from(p: (f64, f64, f64)) -> Self {
+        Self {
+            x: p.0,
+            y: p.1,
+            z: p.2,
+        }
+    }
+}
+
+// The size of the super tetrahedron enclosing all the sites, relative to the size of the bounding box of the sites.
+static SUPER_TETRAHEDRON_SCALE: f64 = 1e3;
+
+/// Provides method for calculating natural neighbor interpolation in 3D.
+///
+/// This includes:
+///  - Cloned point data
+///  - RTree structure to find the tetrahedron as the origin of the boyer-watson cavity
+///  - Delaunay tetrahedralization to construct the boyer-watson cavity for calculating the weight
+///
+/// The sites are weighted by Sibson coordinates: the volume stolen from the voronoi cell of each site
+/// by inserting the target point, which is calculated by clipping the voronoi cell of the target point
+/// with the bisectors between the site and its neighbors.
+///
+/// Points outside the convex hull of the sites are not interpolated.
+/// The sites at exactly the same position as a preceding site are ignored.
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Interpolator3D, Point3D};
+///
+/// let points = [
+///     Point3D { x: 0.0, y: 0.0, z: 0.0 },
+///     Point3D { x: 1.0, y: 0.0, z: 0.0 },
+///     Point3D { x: 0.0, y: 1.0, z: 0.0 },
+///     Point3D { x: 0.0, y: 0.0, z: 1.0 },
+///     Point3D { x: 1.0, y: 1.0, z: 1.0 },
+/// ];
+/// let values = [0.0f64, 1.0, 2.0, 3.0, 6.0];
+///
+/// let interpolator = Interpolator3D::new(&points);
+///
+/// // the values of a linear function are reproduced
+/// let value = interpolator
+///     .interpolate(&values, Point3D { x: 0.3, y: 0.3, z: 0.3 })
+///     .unwrap()
+///     .unwrap();
+/// assert!((value - 1.8).abs() < 1e-6);
+///
+/// let weights = interpolator
+///     .query_weights(Point3D { x: 0.3, y: 0.3, z: 0.3 })
+///     .unwrap()
+///     .unwrap();
+/// assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-6);
+///
+/// // the point outside the convex hull
+/// assert!(interpolator
+///     .interpolate(&values, Point3D { x: 2.0, y: 0.0, z: 0.0 })
+///     .unwrap()
+///     .is_none());
+/// ```
+#[derive(Clone)]
+pub struct Interpolator3D {
+    // the sites followed by the four vertices of the super tetrahedron
+    points: Vec<Point3D>,
+    // the number of the sites
+    sites: usize,
+    // the vertices of the tetrahedra, positively oriented
+    tetrahedra: Vec<[usize; 4]>,
+    // neighbors[t][k] is the tetrahedron sharing the face opposite to the vertex k of the tetrahedron t
+    neighbors: Vec<[usize; 4]>,
+    // a tetrahedron containing each vertex (EMPTY for the ignored sites)
+    vertex_tetrahedra: Vec<usize>,
+    // the tetrahedra made only of the sites
+    tree: rstar::RTree<Tetrahedron>,
+}
+
+impl Interpolator3D {
+    /// Create a new Interpolator3D from a slice of points.
+    pub fn new<P>(points: &[P]) -> Self
+    where
+        P: Into<Point3D> + Clone,
+    {
+        let mut points = points
+            .iter()
+            .map(|p| (*p).clone().into())
+            .collect::<Vec<Point3D>>();
+        let sites = points.len();
+
+        let (min, max) = points.iter().fold(
+            (
+                Point3D {
+                    x: f64::MAX,
+                    y: f64::MAX,
+                    z: f64::MAX,
+                },
+                Point3D {
+                    x: f64::MIN,
+                    y: f64::MIN,
+                    z: f64::MIN,
+                },
+            ),
+            |(min, max), p| {
+                (
+                    Point3D {
+                        x: min.x.min(p.x),
+                        y: min.y.min(p.y),
+                        z: min.z.min(p.z),
+                    },
+                    Point3D {
+                        x: max.x.max(p.x),
+                        y: max.y.max(p.y),
+                        z: max.z.max(p.z),
+                    },
+                )
+            },
+        );
+        let center = if sites == 0 {
+            Point3D::default()
+        } else {
+            Point3D {
+                x: (min.x + max.x) / 2.,
+                y: (min.y + max.y) / 2.,
+                z: (min.z + max.z) / 2.,
+            }
+        };
+        let size = if sites == 0 {
+            1.
+        } else {
+            (max.x - min.x)
+                .max(max.y - min.y)
+                .max(max.z - min.z)
+                .max(1.)
+        };
+
+        // the super tetrahedron is a regular tetrahedron enclosing the bounding box
+        let r = size * SUPER_TETRAHEDRON_SCALE;
+        for [sx, sy, sz] in [[1., 1., 1.], [1., -1., -1.], [-1., 1., -1.], [-1., -1., 1.]] {
+            points.push(Point3D {
+                x: center.x + sx * r,
+                y: center.y + sy * r,
+                z: center.z + sz * r,
+            });
+        }
+
+        let mut interpolator = Self {
+            points,
+            sites,
+            tetrahedra: Vec::new(),
+            neighbors: Vec::new(),
+            vertex_tetrahedra: vec![EMPTY; sites],
+            tree: rstar::RTree::new(),
+        };
+        let mut root = [sites, sites + 1, sites + 2, sites + 3];
+        interpolator.orient_positively(&mut root);
+        interpolator.tetrahedra.push(root);
+        interpolator.neighbors.push([EMPTY; 4]);
+        interpolator.vertex_tetrahedra.extend([0; 4]);
+
+        let mut last = 0;
+        for i in 0..sites {
+            if let Some(t) = interpolator.insert_site(i, last) {
+                last = t;
+            }
+        }
+
+        let tetrahedra = (0..interpolator.tetrahedra.len())
+            .filter(|t| interpolator.tetrahedra[*t].iter().all(|i| *i < sites))
+            .map(|t| {
+                Tetrahedron::from_tetrahedron(&interpolator.points, &interpolator.tetrahedra, t)
+            })
+            .collect::<Vec<_>>();
+        interpolator.tree = rstar::RTree::bulk_load(tetrahedra);
+        interpolator
+    }
+
+    /// Swap the vertices of the tetrahedron if it is negatively oriented.
+    fn orient_positively(&self, tetrahedron: &mut [usize; 4]) {
+        let [a, b, c, d] = tetrahedron.map(|i| &self.points[i]);
+        if orient3d(a, b, c, d) < 0. {
+            tetrahedron.swap(0, 1);
+        }
+    }
+
+    /// Find the tetrahedron containing the point by walking from the tetrahedron `start`.
+    fn walk_to_tetrahedron(&self, ptarget: &Point3D, start: usize) -> Option<usize> {
+        let mut t = start;
+        // the order of the faces is rotated at each step to avoid cycles
+        for step in 0..self.tetrahedra.len() * 4 {
+            let vertices = self.tetrahedra[t].map(|i| &self.points[i]);
+            let exit = (0..4).map(|j| (j + step) % 4).find(|k| {
+                let mut replaced = vertices;
+                replaced[*k] = ptarget;
+                orient3d(replaced[0], replaced[1], replaced[2], replaced[3]) < 0.
+            });
+            match exit {
+                Some(k) => {
+                    t = self.neighbors[t][k];
+                    if t == EMPTY {
+                        return None;
+                    }
+                }
+                None => return Some(t),
+            }
+        }
+        None
+    }
+
+    /// Find the tetrahedron containing the point (including its faces) using the RTree.
+    fn fit_in_tetrahedron(&self, ptarget: &Point3D) -> Option<usize> {
+        self.tree
+            .locate_all_at_point(&[ptarget.x, ptarget.y, ptarget.z])
+            .find(|t| t.point_in_tetrahedron(&self.points, &self.tetrahedra, ptarget))
+            .map(|t| t.itetrahedron())
+    }
+
+    /// Find the tetrahedra whose circumsphere contains the point (the boyer-watson cavity),
+    /// by searching the neighboring tetrahedra from the tetrahedron containing the point.
+    fn find_cavity(&self, ptarget: &Point3D, start: usize) -> Vec<usize> {
+        let mut cavity = vec![start];
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(t) = stack.pop() {
+            for n in self.neighbors[t] {
+                if n == EMPTY || !visited.insert(n) {
+                    continue;
+                }
+                let vertices = self.tetrahedra[n].map(|i| &self.points[i]);
+                if in_circumsphere(&vertices, ptarget) {
+                    cavity.push(n);
+                    stack.push(n);
+                }
+            }
+        }
+        cavity
+    }
+
+    /// The faces on the boundary of the cavity, as (vertices of the face, tetrahedron outside of the face).
+    fn cavity_boundary(&self, cavity: &[usize]) -> Vec<([usize; 3], usize)> {
+        let inside = cavity.iter().copied().collect::<HashSet<_>>();
+        let mut boundary = Vec::new();
+        for t in cavity {
+            for k in 0..4 {
+                let n = self.neighbors[*t][k];
+                if n == EMPTY || !inside.contains(&n) {
+                    let v = self.tetrahedra[*t];
+                    boundary.push(([v[(k + 1) % 4], v[(k + 2) % 4], v[(k + 3) % 4]], n));
+                }
+            }
+        }
+        boundary
+    }
+
+    /// Insert the site into the tetrahedralization, walking from the tetrahedron `start`.
+    /// Returns a tetrahedron containing the new site, or None if the site is ignored.
+    fn insert_site(&mut self, index: usize, start: usize) -> Option<usize> {
+        let point = self.points[index].clone();
+        let t = self
+            .walk_to_tetrahedron(&point, start)
+            .or_else(|| self.walk_to_tetrahedron(&point, 0))?;
+        if self.tetrahedra[t].iter().any(|i| self.points[*i] == point) {
+            // duplicated site
+            return None;
+        }
+
+        let cavity = self.find_cavity(&point, t);
+        let boundary = self.cavity_boundary(&cavity);
+        let mut slots = cavity.clone();
+        while slots.len() < boundary.len() {
+            slots.push(self.tetrahedra.len());
+            self.tetrahedra.push([EMPTY; 4]);
+            self.neighbors.push([EMPTY; 4]);
+        }
+
+        // the faces of the new tetrahedra containing the new site, keyed by the other two vertices
+        let mut faces = HashMap::new();
+        for ((face, outside), slot) in boundary.iter().zip(slots.iter()) {
+            // the face of the outside tetrahedron which was shared with the cavity
+            let shared = if *outside == EMPTY {
+                None
+            } else {
+                (0..4).find(|k| {
+                    let v = self.tetrahedra[*outside][*k];
+                    !face.contains(&v)
+                })
+            };
+
+            let mut tetrahedron = [face[0], face[1], face[2], index];
+            self.orient_positively(&mut tetrahedron);
+            self.tetrahedra[*slot] = tetrahedron;
+            self.neighbors[*slot] = [EMPTY; 4];
+            self.neighbors[*slot][3] = *outside;
+            if let Some(k) = shared {
+                self.neighbors[*outside][k] = *slot;
+            }
+
+            for k in 0..3 {
+                let (a, b) = (tetrahedron[(k + 1) % 3], tetrahedron[(k + 2) % 3]);
+                let key = (a.min(b), a.max(b));
+                if let Some((other, l)) = faces.remove(&key) {
+                    self.neighbors[*slot][k] = other;
+                    self.neighbors[other][l] = *slot;
+                } else {
+                    faces.insert(key, (*slot, k));
+                }
+            }
+            for v in tetrahedron {
+                self.vertex_tetrahedra[v] = *slot;
+            }
+        }
+
+        // the cavity can have more tetrahedra than the new ones
+        let mut left_over = slots[boundary.len()..].to_vec();
+        left_over.sort_unstable_by(|a, b| b.cmp(a));
+        for t in left_over {
+            self.move_last_tetrahedron(t);
+        }
+        Some(self.vertex_tetrahedra[index])
+    }
+
+    /// Move the last tetrahedron into the slot `t` and remove the last slot.
+    fn move_last_tetrahedron(&mut self, t: usize) {
+        let last = self.tetrahedra.len() - 1;
+        if t != last {
+            self.tetrahedra[t] = self.tetrahedra[last];
+            self.neighbors[t] = self.neighbors[last];
+            for n in self.neighbors[t] {
+                if n != EMPTY {
+                    for m in &mut self.neighbors[n] {
+                        if *m == last {
+                            *m = t;
+                        }
+                    }
+                }
+            }
+            for v in self.tetrahedra[t] {
+                self.vertex_tetrahedra[v] = t;
+            }
+        }
+        self.tetrahedra.pop();
+        self.neighbors.pop();
+    }
+
+    /// The vertices connected to the vertex by the edges of the tetrahedralization.
+    fn vertex_neighbors(&self, v: usize) -> HashSet<usize> {
+        let start = self.vertex_tetrahedra[v];
+        let mut neighbors = HashSet::new();
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(t) = stack.pop() {
+            for k in 0..4 {
+                let u = self.tetrahedra[t][k];
+                if u == v {
+                    continue;
+                }
+                neighbors.insert(u);
+                // the face opposite to u contains v
+                let n = self.neighbors[t][k];
+                if n != EMPTY && visited.insert(n) {
+                    stack.push(n);
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Perform natural neighbor interpolation.
+    ///
+    /// The 'apply_weight' function is called if the point is iterated as one of the natural neighbors.
+    /// The first argument is the index of the point, the second argument is the weight of the point,
+    /// and the third argument is the tentative sum of the weight.
+    fn perform_interpoation(
+        &self,
+        ptarget: &Point3D,
+        apply_weight: &mut impl FnMut(usize, f64, f64),
+    ) -> Result<(), InterpolatorError> {
+        let start = if let Some(t) = self.fit_in_tetrahedron(ptarget) {
+            t
+        } else {
+            return Ok(());
+        };
+
+        if let Some(i) = self.tetrahedra[start]
+            .iter()
+            .find(|i| self.points[**i] == *ptarget)
+        {
+            apply_weight(*i, 1., 1.);
+            return Ok(());
+        }
+
+        let cavity = self.find_cavity(ptarget, start);
+        let natural_neighbors = cavity
+            .iter()
+            .flat_map(|t| self.tetrahedra[*t])
+            .collect::<HashSet<_>>();
+
+        // The voronoi cell of the target point is the convex hull of the circumcenters of the new tetrahedra.
+        let mut min = [f64::MAX; 3];
+        let mut max = [f64::MIN; 3];
+        for (face, _) in self.cavity_boundary(&cavity) {
+            let [a, b, c] = face.map(|i| &self.points[i]);
+            let center = circumcenter3d(&[a, b, c, ptarget]);
+            for (k, x) in [center.x, center.y, center.z].into_iter().enumerate() {
+                if x.is_finite() {
+                    min[k] = min[k].min(x);
+                    max[k] = max[k].max(x);
+                }
+            }
+        }
+        let margin = (0..3).map(|k| max[k] - min[k]).fold(0., f64::max) * 1e-6;
+        let mut cell = ConvexPolyhedron::aabb(
+            &Point3D {
+                x: min[0] - margin,
+                y: min[1] - margin,
+                z: min[2] - margin,
+            },
+            &Point3D {
+                x: max[0] + margin,
+                y: max[1] + margin,
+                z: max[2] + margin,
+            },
+        );
+        for j in &natural_neighbors {
+            cell.clip_bisector(ptarget, &self.points[*j]);
+        }
+
+        // the weight of the site is the volume of the cell of the target point inside the cell of the site
+        let mut natural_neighbors = natural_neighbors
+            .into_iter()
+            .filter(|i| *i < self.sites)
+            .collect::<Vec<_>>();
+        natural_neighbors.sort_unstable();
+
+        let mut tmp_weight_sum = 0.;
+        for i in natural_neighbors {
+            let mut stolen = cell.clone();
+            for k in self.vertex_neighbors(i) {
+                stolen.clip_bisector(&self.points[i], &self.points[k]);
+            }
+            let weight = stolen.volume();
+            if weight > 0. {
+                tmp_weight_sum += weight;
+                apply_weight(i, weight, tmp_weight_sum);
+            }
+        }
+        Ok(())
+    }
+
+    /// Interpolate the value at the point.
+    /// If the point is outside the convex hull of the sites, None is returned.
+    pub fn interpolate<P, V>(
+        &self,
+        values: &[V],
+        ptarget: P,
+    ) -> Result<Option<V>, InterpolatorError>
+    where
+        P: Into<Point3D>,
+        V: Lerpable,
+    {
+        if self.sites != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let mut terms = Vec::new();
+        self.perform_interpoation(&ptarget.into(), &mut |i, weight, _| {
+            terms.push((&values[i], weight));
+        })?;
+
+        Ok((!terms.is_empty()).then(|| V::blend(&terms)))
+    }
+
+    /// Query the result of the interpolation as a list of indices of sites to be weighted.
+    /// If the point is outside the convex hull of the sites, None is returned.
+    pub fn query_weights<P>(
+        &self,
+        ptarget: P,
+    ) -> Result<Option<Vec<(usize, f64)>>, InterpolatorError>
+    where
+        P: Into<Point3D>,
+    {
+        let mut weights = Vec::new();
+        let mut weight_sum = 0.;
+        self.perform_interpoation(&ptarget.into(), &mut |i, weight, _| {
+            weight_sum += weight;
+            weights.push((i, weight));
+        })?;
+
+        if weight_sum == 0. {
+            Ok(None)
+        } else {
+            Ok(Some(
+                weights.iter().map(|(i, w)| (*i, w / weight_sum)).collect(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::marker::PhantomData;
+
+use crate::{weighted_sum::normalized, Lerpable, WeightedSum};
+
+/// Defines how the interpolated values of integers are rounded.
+///
+/// Used as the parameter of [Rounded].
+pub trait Rounding: Clone {
+    /// Round the interpolated value.
+    fn round(value: f64) -> f64;
+}
+
+/// Rounds to the nearest integer (half away from zero). This is used for the integers by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Nearest;
+
+/// Rounds toward negative infinity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Floor;
+
+/// Rounds toward positive infinity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Ceil;
+
+/// Rounds toward zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Truncate;
+
+impl Rounding for Nearest {
+    fn round(value: f64) -> f64 {
+        value.round()
+    }
+}
+
+impl Rounding for Floor {
+    fn round(value: f64) -> f64 {
+        value.floor()
+    }
+}
+
+impl Rounding for Ceil {
+    fn round(value: f64) -> f64 {
+        value.ceil()
+    }
+}
+
+impl Rounding for Truncate {
+    fn round(value: f64) -> f64 {
+        value.trunc()
+    }
+}
+
+/// An integer value interpolated with the rounding `R`.
+///
+/// The integers (such as `u8` and `i32`) are rounded to the nearest integer by default,
+/// and clamped to the range of the type.
+/// Wrap them with this type to choose another rounding.
+///
+/// The values of the natural neighbors are summed up in `f64`, and the sum is rounded only once (see `Lerpable::blend`).
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Floor, Interpolator, Point, Rounded};
+///
+/// let points = [
+///     Point { x: 0.0, y: 0.0 },
+///     Point { x: 1.0, y: 0.0 },
+///     Point { x: 0.0, y: 1.0 },
+/// ];
+///
+/// let interpolator = Interpolator::new(&points);
+///
+/// let values = [0u8, 3, 3];
+/// let value = interpolator
+///     .interpolate(&values, Point { x: 0.5, y: 0.0 })
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(value, 2);
+///
+/// let values = values.map(Rounded::<u8, Floor>::new);
+/// let value = interpolator
+///     .interpolate(&values, Point { x: 0.5, y: 0.0 })
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(value.value, 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rounded<T, R = Nearest> {
+    pub value: T,
+    rounding: PhantomData<R>,
+}
+
+impl<T, R> Rounded<T, R> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            rounding: PhantomData,
+        }
+    }
+}
+
+impl Lerpable for f64 {
+    fn lerp(&self, other: &Self, weight: f64) -> Self {
+        self * (1.0 - weight) + other * weight
+    }
+
+    fn blend(terms: &[(&Self, f64)]) -> Self {
+        Self::weighted_sum(&normalized(terms))
+    }
+}
+
+impl Lerpable for f32 {
+    fn lerp(&self, other: &Self, weight: f64) -> Self {
+        (*self as f64).lerp(&(*other as f64), weight) as f32
+    }
+
+    fn blend(terms: &[(&Self, f64)]) -> Self {
+        Self::weighted_sum(&normalized(terms))
+    }
+}
+
+macro_rules! impl_lerpable_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Lerpable for $t {
+                fn lerp(&self, other: &Self, weight: f64) -> Self {
+                    Rounded::<$t>::new(*self).lerp(&Rounded::new(*other), weight).value
+                }
+
+                fn blend(terms: &[(&Self, f64)]) -> Self {
+                    Self::weighted_sum(&normalized(terms))
+                }
+            }
+
+            impl<R> Lerpable for Rounded<$t, R>
+            where
+                R: Rounding,
+            {
+                fn lerp(&self, other: &Self, weight: f64) -> Self {
+                    // the conversion by `as` saturates at the bounds of the type
+                    let value = (self.value as f64).lerp(&(other.value as f64), weight);
+                    Self::new(R::round(value) as $t)
+                }
+
+                fn blend(terms: &[(&Self, f64)]) -> Self {
+                    Self::weighted_sum(&normalized(terms))
+                }
+            }
+        )*
+    };
+}
+
+impl_lerpable_for_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl<V, const N: usize> Lerpable for [V; N]
+where
+    V: Lerpable,
+{
+    fn lerp(&self, other: &Self, weight: f64) -> Self {
+        std::array::from_fn(|i| self[i].lerp(&other[i], weight))
+    }
+
+    fn blend(terms: &[(&Self, f64)]) -> Self {
+        std::array::from_fn(|i| {
+            V::blend(&terms.iter().map(|(v, w)| (&v[i], *w)).collect::<Vec<_>>())
+        })
+    }
+}
+
+macro_rules! impl_lerpable_for_tuple {
+    ($(($($v:ident $i:tt),*)),*) => {
+        $(
+            impl<$($v),*> Lerpable for ($($v,)*)
+            where
+                $($v: Lerpable),*
+            {
+                fn lerp(&self, other: &Self, weight: f64) -> Self {
+                    ($(self.$i.lerp(&other.$i, weight),)*)
+                }
+
+                fn blend(terms: &[(&Self, f64)]) -> Self {
+                    ($($v::blend(&terms.iter().map(|(v, w)| (&v.$i, *w)).collect::<Vec<_>>()),)*)
+                }
+            }
+        )*
+    };
+}
+
+impl_lerpable_for_tuple!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3)
+);
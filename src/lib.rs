@@ -7,13 +7,57 @@
 //! ## Documentation
 //!
 //! See the [Interpolator] struct for the main documentation of this crate.
+//! See the [Interpolator3D] struct for the interpolation in 3D.
+//! See the [SphericalInterpolator] struct for the interpolation on the sphere.
 //!
+use std::collections::{HashMap, HashSet};
+
 use primitives::Triangle;
 use thiserror::Error;
-use util::{circumcenter, circumcircle_with_radius_2, next_harfedge};
-
+use util::{circumcenter, circumcenter_derivative, distance_2, in_circumcircle, next_harfedge};
+
+mod breakline;
+mod coordinates;
+mod dedup;
+mod domain;
+mod extrapolation;
+mod gradient;
+mod grid;
+mod incremental;
+mod interpolator3d;
+mod lerp;
+mod linear;
+mod location;
+mod multi;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod periodic;
+mod polyhedron;
 mod primitives;
+#[cfg(feature = "serde")]
+mod serialization;
+mod smooth;
+mod sparse;
+mod spherical;
 mod util;
+mod validation;
+mod voronoi;
+mod walk;
+mod weighted_sum;
+
+pub use coordinates::CoordinateKind;
+pub use dedup::MergePolicy;
+pub use domain::Domain;
+pub use extrapolation::ExtrapolationMode;
+pub use gradient::GradientEstimation;
+pub use grid::GridSpec;
+pub use interpolator3d::{Interpolator3D, Point3D};
+pub use lerp::{Ceil, Floor, Nearest, Rounded, Rounding, Truncate};
+pub use location::Location;
+pub use sparse::SparseMatrix;
+pub use spherical::SphericalInterpolator;
+pub use walk::QueryHint;
+pub use weighted_sum::WeightedSum;
 
 /// Represents a 2D point.
 pub type Point = delaunator::Point;
@@ -21,7 +65,9 @@ pub type Point = delaunator::Point;
 /// Defines objects that can apply linear interpolation.
 ///
 /// The value to be interpolated must implement this trait.
-/// `f64` implements this trait by default.
+/// This trait is implemented for `f64`, `f32`, the integers (rounded to the nearest, see [Rounded] for the other roundings),
+/// and the arrays and the tuples (up to 4 elements) of them.
+/// See also [WeightedSum] to calculate the value from all the natural neighbors at once.
 ///
 /// # Example
 /// ```
@@ -47,16 +93,22 @@ pub type Point = delaunator::Point;
 pub trait Lerpable: Clone {
     /// Apply linear interpolation with weight (0.0-1.0).
     fn lerp(&self, other: &Self, weight: f64) -> Self;
-}
 
-// Implementation of Lerpable for all float values that can convert to f64
-impl<V> Lerpable for V
-where
-    V: Into<f64> + From<f64> + Copy,
-{
-    fn lerp(&self, other: &Self, weight: f64) -> Self {
-        let result_f64 = (*self).into() * (1.0 - weight) + (*other).into() * weight;
-        result_f64.into()
+    /// Blend the values of the natural neighbors with their weights.
+    /// The terms are not empty, and the weights are not normalized.
+    ///
+    /// By default, the values are blended one by one with `lerp`.
+    /// Override this to calculate the value from all the terms at once.
+    /// The floats and the integers are summed up by the compensated summation of [WeightedSum],
+    /// and the integers are rounded only once.
+    fn blend(terms: &[(&Self, f64)]) -> Self {
+        let mut value = terms[0].0.clone();
+        let mut weight_sum = terms[0].1;
+        for (other, weight) in &terms[1..] {
+            weight_sum += weight;
+            value = value.lerp(other, weight / weight_sum);
+        }
+        value
     }
 }
 
@@ -70,6 +122,13 @@ where
 /// Use `interpolate(&self, values: &[V], ptarget: P)` to interpolate the value at the point.
 /// Use `query_weights(&self, ptarget: P)` to query the result of the interpolation as a list of indices of sites to be weighted.
 ///
+/// The sites are weighted by Sibson coordinates by default.
+/// Use `with_coordinate_kind` to choose another kind of natural neighbor coordinates (see [CoordinateKind]).
+///
+/// Points outside the convex hull of the sites are not interpolated by default.
+/// Use `with_extrapolation_mode` to choose how to extrapolate them (see [ExtrapolationMode]).
+/// Use `new_periodic` for the sites repeated periodically in x and y.
+///
 /// # Example
 ///
 /// ```
@@ -91,7 +150,7 @@ where
 /// ];
 ///
 /// let values = [
-///     1.0, 0.0, 1.0, 0.0
+///     1.0f64, 0.0, 1.0, 0.0
 /// ];
 ///
 /// // Create an interpolator from the points.
@@ -119,18 +178,31 @@ where
 #[derive(Clone)]
 pub struct Interpolator {
     points: Vec<Point>,
+    // vacant[i] is true if the site i has been removed
+    vacant: Vec<bool>,
+    // merged[i] is the index of the site which the site i is merged into (i itself if not merged)
+    merged: Vec<usize>,
+    // the sites merged into each site (including itself), only for the sites with duplicates
+    groups: HashMap<usize, Vec<usize>>,
+    merge_tolerance: f64,
+    merge_policy: MergePolicy,
     triangles: Vec<usize>,
     harfedges: Vec<usize>,
+    // the edges which must not be crossed, as pairs of the sites in ascending order
+    breaklines: HashSet<(usize, usize)>,
     tree: rstar::RTree<Triangle>,
     degree_limitation: usize,
+    extrapolation_mode: ExtrapolationMode,
+    coordinate_kind: CoordinateKind,
+    domain: Option<Domain>,
+    // in_domain[i] is true if the site i is inside the domain (empty if no domain is set)
+    in_domain: Vec<bool>,
+    // interpolate linearly along the line if the sites are collinear
+    linear_fallback: bool,
+    // the sites are followed by their ghost copies in the periodic mode
+    periodicity: Option<periodic::Periodicity>,
 }
 
-// The epsiron value for the interpolator.
-// This is used to move the point slightly when the point is on the edge of the triangulation.
-// because calculating the weight of the point on the edge is not stable.
-// This value must be greater than primitives::EPS_TRIANGLE.
-static EPS_INTERPOLATOR: f64 = 1e-12;
-
 // The default degree limitation of the interpolator.
 static DEFAULT_DEGREE_LIMITATION: usize = 30;
 
@@ -143,10 +215,55 @@ pub enum InterpolatorError {
     TooManyNeighbors(usize),
     #[error("The number of points and values are not the same.")]
     DifferentNumberOfPointsAndValues,
+    #[error("The number of points and gradients are not the same.")]
+    DifferentNumberOfPointsAndGradients,
+    /// This error occurs when the sites closer than the tolerance are found with `MergePolicy::Error`.
+    #[error("The sites ({0}) and ({1}) are closer than the tolerance.")]
+    DuplicatedSites(usize, usize),
+    /// This error occurs when the index of the site does not exist or the site has already been removed.
+    #[error("The site ({0}) does not exist or has already been removed.")]
+    InvalidSiteIndex(usize),
+    /// This error occurs when there are too few distinct points to be triangulated.
+    #[error("Too few distinct points are given to be triangulated.")]
+    TooFewPoints,
+    /// This error occurs when all the points are on a line and cannot be triangulated.
+    /// Use `Interpolator::try_new_with_linear_fallback` to interpolate them linearly.
+    #[error("All the points are collinear.")]
+    Collinear,
+    /// This error occurs when the coordinate of the point is NaN or infinite.
+    #[error("The point ({0}) has a non-finite coordinate.")]
+    NonFiniteCoordinate(usize),
+    /// This error occurs when the breaklines (given as the pairs of the sites) cross each other.
+    #[error("The breaklines {0:?} and {1:?} cross each other.")]
+    CrossingBreaklines((usize, usize), (usize, usize)),
+    /// This error occurs when the harfedge is not paired with its opposite harfedge, or the triangles do not share the edge.
+    #[error("The harfedge ({0}) is inconsistent with the triangles.")]
+    InvalidHarfedge(usize),
+    /// This error occurs when the triangle is degenerate or not clockwise, as the triangles given by `delaunator`.
+    #[error("The triangle ({0}) is degenerate or not clockwise.")]
+    InvalidTriangle(usize),
+    /// This error occurs when the site is inside the circumcircle of the adjacent triangle.
+    #[error("The site ({1}) is inside the circumcircle of the triangle ({0}), so the triangulation is not Delaunay.")]
+    NotDelaunay(usize, usize),
+    /// This error occurs when the boundary of the triangulation is not convex at the site.
+    #[error("The boundary of the triangulation is not convex at the site ({0}), so the triangulation is not Delaunay.")]
+    NonConvexHull(usize),
+    /// This error occurs when the site is not a vertex of any triangle of the given triangulation.
+    #[error("The site ({0}) is not a vertex of any triangle.")]
+    UnusedSite(usize),
+    /// This error occurs when the period of the periodic interpolator is not finite and positive.
+    #[error("The period ({0}) is not finite and positive.")]
+    InvalidPeriod(f64),
+    /// This error occurs when the site is not in the domain of one period starting from the minimum corner of the sites.
+    #[error("The site ({0}) is outside the domain of one period.")]
+    SiteOutsidePeriod(usize),
 }
 
 impl Interpolator {
     /// Create a new Interpolator from a slice of points.
+    ///
+    /// If the points cannot be triangulated, the interpolator is created but interpolates nothing.
+    /// Use `try_new` to detect it.
     pub fn new<P>(points: &[P]) -> Self
     where
         P: Into<Point> + Clone,
@@ -156,23 +273,54 @@ impl Interpolator {
             .map(|p| (*p).clone().into())
             .collect::<Vec<Point>>();
 
-        let triangulation = delaunator::triangulate(&points);
+        let merged = dedup::merge_duplicates(&points, 0.);
+        Self::from_merged_sites(points, merged, 0., MergePolicy::default())
+    }
 
-        let circumcircles = triangulation
-            .triangles
-            .chunks_exact(3)
-            .enumerate()
-            .map(|(t, _)| Triangle::from_triangle(&points, &triangulation.triangles, t))
-            .collect::<Vec<_>>();
+    /// Create a new Interpolator from the points and the mapping of the merged sites, and triangulate them.
+    fn from_merged_sites(
+        points: Vec<Point>,
+        merged: Vec<usize>,
+        merge_tolerance: f64,
+        merge_policy: MergePolicy,
+    ) -> Self {
+        let mut interpolator = Self::untriangulated(points, merged, merge_tolerance, merge_policy);
+        interpolator.retriangulate();
+        interpolator
+    }
 
-        let rtree = rstar::RTree::bulk_load(circumcircles);
+    /// Create a new Interpolator from the points and the mapping of the merged sites, without the triangulation.
+    fn untriangulated(
+        points: Vec<Point>,
+        merged: Vec<usize>,
+        merge_tolerance: f64,
+        merge_policy: MergePolicy,
+    ) -> Self {
+        let mut groups = HashMap::<usize, Vec<usize>>::new();
+        for (i, site) in merged.iter().enumerate() {
+            if i != *site {
+                groups.entry(*site).or_insert_with(|| vec![*site]).push(i);
+            }
+        }
 
         Self {
+            vacant: vec![false; points.len()],
             points,
-            triangles: triangulation.triangles,
-            harfedges: triangulation.halfedges,
-            tree: rtree,
+            merged,
+            groups,
+            merge_tolerance,
+            merge_policy,
+            triangles: Vec::new(),
+            harfedges: Vec::new(),
+            breaklines: HashSet::new(),
+            tree: rstar::RTree::new(),
             degree_limitation: DEFAULT_DEGREE_LIMITATION,
+            extrapolation_mode: ExtrapolationMode::default(),
+            coordinate_kind: CoordinateKind::default(),
+            domain: None,
+            in_domain: Vec::new(),
+            linear_fallback: false,
+            periodicity: None,
         }
     }
 
@@ -186,20 +334,91 @@ impl Interpolator {
         interpolator
     }
 
+    /// Set how to extrapolate the points outside the convex hull of the sites.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{ExtrapolationMode, Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0];
+    ///
+    /// let interpolator =
+    ///     Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::NearestSite);
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 2.0, y: -0.5 })
+    ///     .unwrap();
+    /// assert_eq!(value, Some(1.0));
+    /// ```
+    pub fn with_extrapolation_mode(mut self, extrapolation_mode: ExtrapolationMode) -> Self {
+        self.extrapolation_mode = extrapolation_mode;
+        self
+    }
+
+    /// Set the kind of the natural neighbor coordinates used as the weights of the sites.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{CoordinateKind, Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// let interpolator =
+    ///     Interpolator::new(&points).with_coordinate_kind(CoordinateKind::Laplace);
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 0.5, y: 0.5 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn with_coordinate_kind(mut self, coordinate_kind: CoordinateKind) -> Self {
+        self.coordinate_kind = coordinate_kind;
+        self
+    }
+
     fn detect_too_large_degree(&self, dct: usize) -> bool {
         dct >= self.degree_limitation - 1
     }
 
     // edges.0 -> edges.1 -> edges.2
+    // Returns the weight of the site of edges.1 and its gradient with respect to the target point.
+    // For Sibson coordinates, the weight is the (doubled) area stolen from the site.
     fn calculate_weight_area(
         &self,
         ptarget: &Point,
         edges: (usize, usize, usize),
-    ) -> Result<f64, InterpolatorError> {
+        kind: CoordinateKind,
+    ) -> Result<(f64, [f64; 2]), InterpolatorError> {
         let point_prev = &self.points[self.triangles[edges.0]];
         let point_base = &self.points[self.triangles[edges.1]];
         let point_next = &self.points[self.triangles[edges.2]];
 
+        if kind == CoordinateKind::Laplace {
+            // The envelope is clockwise, so gnext -> gprev is counterclockwise around the target point.
+            let gprev = circumcenter(&[ptarget, point_base, point_prev]);
+            let gnext = circumcenter(&[ptarget, point_base, point_next]);
+            return Ok(coordinates::laplace_weight(
+                ptarget,
+                point_base,
+                &gnext,
+                &gprev,
+                circumcenter_derivative(ptarget, point_base, point_next),
+                circumcenter_derivative(ptarget, point_base, point_prev),
+            ));
+        }
+
         let mprev = &Point {
             x: (point_base.x + point_prev.x) / 2.,
             y: (point_base.y + point_prev.y) / 2.,
@@ -245,81 +464,162 @@ impl Interpolator {
             + (gnext.x - mnext.x) * (gnext.y + mnext.y)
             + (mnext.x - mprev.x) * (mnext.y + mprev.y);
 
-        Ok(pre - post)
+        // The derivative of the stolen area is given by the voronoi edge between the target point and the site (gprev-gnext):
+        // length / distance * (midpoint - target)
+        let length = ((gnext.x - gprev.x).powi(2) + (gnext.y - gprev.y).powi(2)).sqrt();
+        let distance =
+            ((point_base.x - ptarget.x).powi(2) + (point_base.y - ptarget.y).powi(2)).sqrt();
+        let scale = 2. * length / distance;
+        let gradient = [
+            scale * ((gprev.x + gnext.x) / 2. - ptarget.x),
+            scale * ((gprev.y + gnext.y) / 2. - ptarget.y),
+        ];
+
+        Ok((pre - post, gradient))
     }
 
-    fn fit_in_triangle(&self, ptarget: &Point, check_around: bool) -> Option<(usize, Point)> {
-        let triangles = self
-            .tree
+    /// Find a triangle containing the point (including its edges) using the RTree.
+    pub(crate) fn fit_in_triangle(&self, ptarget: &Point) -> Option<usize> {
+        self.tree
             .locate_all_at_point(&[ptarget.x, ptarget.y])
-            .filter(|circle| circle.point_in_triangle(&self.points, &self.triangles, ptarget))
-            .collect::<Vec<_>>();
-
-        if triangles.len() >= 2 {
-            if !check_around {
-                return None;
-            }
-            let eps = EPS_INTERPOLATOR;
-
-            // random (mannually selected) points around the target point
-            let check_angles = [
-                Point {
-                    x: eps * 1.415,
-                    y: eps * 1.339,
-                },
-                Point {
-                    x: eps * 1.335,
-                    y: -eps * 1.483,
-                },
-                Point {
-                    x: -eps * 1.421,
-                    y: -eps * 1.384,
-                },
-                Point {
-                    x: -eps * 1.498,
-                    y: eps * 1.322,
-                },
-            ];
-
-            for angle in check_angles {
-                let check_point = Point {
-                    x: ptarget.x + angle.x,
-                    y: ptarget.y + angle.y,
-                };
-                if let Some(t) = self.fit_in_triangle(&check_point, false) {
-                    return Some(t);
-                }
-            }
-
-            return None;
-        }
-
-        triangles
-            .get(0)
-            .map(|t| (t.itriangle() * 3, ptarget.clone()))
+            .find(|t| t.point_in_triangle(&self.points, &self.triangles, ptarget))
+            .map(|t| t.itriangle())
     }
 
     /// Perform natural neighbor interpolation.
     ///
     /// The 'apply_weight' function is called if the point is iterated as one of the natural neighbors.
-    /// The first argument is the index of the point, the second argument is the weight of the point, and the third argument is the tentative sum of the weight.
+    /// The first argument is the index of the point, the second argument is the weight of the point,
+    /// the third argument is the gradient of the weight with respect to the target point, and the fourth argument is the tentative sum of the weight.
     /// See the implementation of `Interpolator::interpolate` as an example.
+    /// Returns where the point is located in the triangulation.
     fn perform_interpoation<P>(
         &self,
         ptarget: P,
-        apply_weight: &mut impl FnMut(usize, f64, f64),
-    ) -> Result<(), InterpolatorError>
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> Result<Location, InterpolatorError>
     where
         P: Into<Point> + Clone,
     {
-        let ptarget = ptarget.into();
+        self.perform_interpoation_with_hint(
+            ptarget.into(),
+            &mut QueryHint::default(),
+            self.coordinate_kind,
+            apply_weight,
+        )
+    }
+
+    /// Perform natural neighbor interpolation, starting the search of the triangle from `hint`.
+    ///
+    /// `hint` holds the triangle found in the previous call for a nearby point, and is updated to the triangle found in this call.
+    /// The weights are the natural neighbor coordinates of `kind`, regardless of the kind set to the interpolator.
+    fn perform_interpoation_with_hint(
+        &self,
+        ptarget: Point,
+        hint: &mut QueryHint,
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> Result<Location, InterpolatorError> {
+        let ptarget = self.wrap_periodic(ptarget);
+
+        // exclude the sites outside the domain
+        let mut excluded_weight = 0.;
+        let apply_weight = &mut |i, weight, gradient, tmp_weight_sum| {
+            if self.site_in_domain(i) {
+                apply_weight(i, weight, gradient, tmp_weight_sum - excluded_weight);
+            } else {
+                excluded_weight += weight;
+            }
+        };
+
+        // distribute the weights of the merged sites
+        let apply_weight = &mut |i, weight, gradient, tmp_weight_sum| {
+            self.apply_merged_weight(i, weight, gradient, tmp_weight_sum, apply_weight)
+        };
+
+        // map the ghost copies to the sites in the periodic mode
+        let apply_weight = &mut |i, weight, gradient, tmp_weight_sum| {
+            apply_weight(self.site_of(i), weight, gradient, tmp_weight_sum)
+        };
+
+        if self.domain_contains(&ptarget) {
+            return self.perform_interpoation_in_triangulation(ptarget, hint, kind, apply_weight);
+        }
+
+        // The points outside the domain are extrapolated from the sites inside the domain.
+        match self.extrapolation_mode {
+            ExtrapolationMode::NearestHullEdge => {
+                // the boundary of the domain takes the place of the convex hull
+                if let Some(pboundary) = self.nearest_domain_point(&ptarget) {
+                    self.perform_interpoation_in_triangulation(
+                        pboundary,
+                        hint,
+                        kind,
+                        apply_weight,
+                    )?;
+                }
+            }
+            ExtrapolationMode::GhostPoints => {
+                self.perform_interpoation_in_triangulation(ptarget, hint, kind, apply_weight)?;
+            }
+            _ => self.extrapolate(&ptarget, kind, apply_weight)?,
+        }
+        Ok(Location::Outside)
+    }
+
+    /// Perform natural neighbor interpolation in the triangulation, regardless of the domain.
+    fn perform_interpoation_in_triangulation(
+        &self,
+        ptarget: Point,
+        hint: &mut QueryHint,
+        kind: CoordinateKind,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> Result<Location, InterpolatorError> {
+        if self.is_linear() {
+            return Ok(self.perform_linear_interpolation(&ptarget, apply_weight));
+        }
 
         // initial edge
-        let (start, ptarget) = if let Some(t) = self.fit_in_triangle(&ptarget, true) {
-            t
+        let found = hint
+            .triangle
+            .and_then(|it| self.walk_to_triangle(&ptarget, it))
+            .or_else(|| self.fit_in_triangle(&ptarget));
+        let start = if let Some(it) = found {
+            it * 3
         } else {
-            return Ok(());
+            self.extrapolate(&ptarget, kind, apply_weight)?;
+            return Ok(Location::Outside);
         };
+        hint.triangle = Some(start / 3);
+
+        // The boyer-watson envelope cannot be used if the point is on a site or on the convex hull.
+        let location = self.location_in_triangle(&ptarget, start / 3);
+        if let Location::OnSite(i) = location {
+            apply_weight(i, 1., [0., 0.], 1.);
+            return Ok(location);
+        }
+
+        // The ghost points bound the voronoi cell of the point near the convex hull as well as outside of it.
+        let on_breakline =
+            matches!(location, Location::OnEdge(ia, ib) if self.is_breakline_between(ia, ib));
+        if self.extrapolation_mode == ExtrapolationMode::GhostPoints
+            && !on_breakline
+            && self.apply_ghost_point_weights_inside(&ptarget, start / 3, kind, apply_weight)
+        {
+            return Ok(location);
+        }
+
+        // the breakline is a boundary of the envelope as well as the convex hull
+        if let Location::OnHullEdge(ia, ib) | Location::OnEdge(ia, ib) = location {
+            if matches!(location, Location::OnHullEdge(..)) || on_breakline {
+                let (a, b) = (&self.points[ia], &self.points[ib]);
+                // The natural neighbor coordinates on the convex hull are linear along the edge.
+                let t = ((ptarget.x - a.x) * (b.x - a.x) + (ptarget.y - a.y) * (b.y - a.y))
+                    / distance_2(a, b);
+                self.apply_edge_weights(ia, ib, t, apply_weight);
+                return Ok(location);
+            }
+        }
 
         // Stream of edges on the boyer-watson envelope.
         // edges.0 -> edges.1 -> edges.2
@@ -338,9 +638,9 @@ impl Interpolator {
         // apply the weight.
         let mut apply =
             |edges: (usize, usize, usize), tmp_weight_sum: f64| -> Result<f64, InterpolatorError> {
-                let weight = self.calculate_weight_area(&ptarget, edges)?;
+                let (weight, gradient) = self.calculate_weight_area(&ptarget, edges, kind)?;
                 let tmp_weight_sum: f64 = tmp_weight_sum + weight;
-                apply_weight(self.triangles[edges.1], weight, tmp_weight_sum);
+                apply_weight(self.triangles[edges.1], weight, gradient, tmp_weight_sum);
                 Ok(tmp_weight_sum)
             };
 
@@ -350,8 +650,9 @@ impl Interpolator {
                 for dcount in 0..self.degree_limitation {
                     let opposite = self.harfedges[edge2];
 
-                    // if the opposite is not found (the triangle is on the edge of the triangulation), break the loop.
-                    if opposite >= self.harfedges.len() {
+                    // if the opposite is not found (the triangle is on the edge of the triangulation)
+                    // or the edge is a breakline, break the loop.
+                    if opposite >= self.harfedges.len() || self.is_breakline(edge2) {
                         break;
                     }
 
@@ -362,12 +663,8 @@ impl Interpolator {
                         &self.points[self.triangles[oit * 3 + 2]],
                     ];
 
-                    // circumcicle of the triangle
-                    let (c, r2) = circumcircle_with_radius_2(&triangle_points);
-
                     // check if the point is in the circumcircle
-                    let dist2 = (c.x - ptarget.x).powi(2) + (c.y - ptarget.y).powi(2);
-                    if dist2 < r2 {
+                    if in_circumcircle(&triangle_points, &ptarget) {
                         edge2 = next_harfedge(opposite);
                     } else {
                         break;
@@ -405,11 +702,11 @@ impl Interpolator {
                 return Err(InterpolatorError::TooManyNeighbors(self.degree_limitation));
             }
         }
-        Ok(())
+        Ok(location)
     }
 
     /// Interpolate the value at the point.
-    /// If the point is outside the triangulation, None is returned.
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
     pub fn interpolate<P, V>(
         &self,
         values: &[V],
@@ -419,26 +716,41 @@ impl Interpolator {
         P: Into<Point> + Clone,
         V: Lerpable,
     {
-        if self.points.len() != values.len() {
+        if self.site_count() != values.len() {
             return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
         }
 
-        let mut value: Option<V> = None;
-        self.perform_interpoation::<P>(ptarget, &mut |i, weight, tmp_weight_sum| {
-            let vbase = &values[i];
-            let new_value = if let Some(value) = &value {
-                Some(value.lerp(vbase, weight / tmp_weight_sum))
-            } else {
-                Some(vbase.clone())
-            };
-            value = new_value;
-        })?;
+        self.interpolate_with_hint(values, ptarget.into(), &mut QueryHint::default())
+            .map(|(value, _)| value)
+    }
 
-        Ok(value)
+    /// Interpolate the value at the point, without checking the number of the values.
+    /// Returns the value and where the point is located.
+    fn interpolate_with_hint<V>(
+        &self,
+        values: &[V],
+        ptarget: Point,
+        hint: &mut QueryHint,
+    ) -> Result<(Option<V>, Location), InterpolatorError>
+    where
+        V: Lerpable,
+    {
+        let mut terms = Vec::new();
+        let location = self.perform_interpoation_with_hint(
+            ptarget,
+            hint,
+            self.coordinate_kind,
+            &mut |i, weight, _, _| {
+                terms.push((&values[i], weight));
+            },
+        )?;
+
+        let value = (!terms.is_empty()).then(|| V::blend(&terms));
+        Ok((value, self.site_location(location)))
     }
 
     /// Query the result of the interpolation as a list of indices of sites to be weighted.
-    /// If the point is outside the triangulation, None is returned.
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
     pub fn query_weights<P>(
         &self,
         ptarget: P,
@@ -446,12 +758,31 @@ impl Interpolator {
     where
         P: Into<Point> + Clone,
     {
+        self.query_weights_of_kind(ptarget.into(), self.coordinate_kind)
+    }
+
+    /// Query the weights of the sites given by the natural neighbor coordinates of `kind`.
+    pub(crate) fn query_weights_of_kind(
+        &self,
+        ptarget: Point,
+        kind: CoordinateKind,
+    ) -> Result<Option<Vec<(usize, f64)>>, InterpolatorError> {
         let mut weights = Vec::new();
         let mut weight_sum = 0.;
-        self.perform_interpoation::<P>(ptarget, &mut |i, weight, _| {
-            weight_sum += weight;
-            weights.push((i, weight));
-        })?;
+        self.perform_interpoation_with_hint(
+            ptarget,
+            &mut QueryHint::default(),
+            kind,
+            &mut |i, weight, _, _| {
+                weight_sum += weight;
+                // the copies of a site in the periodic mode are aggregated
+                if let Some((_, w)) = weights.iter_mut().find(|(j, _)| *j == i) {
+                    *w += weight;
+                } else {
+                    weights.push((i, weight));
+                }
+            },
+        )?;
 
         if weight_sum == 0. {
             Ok(None)
@@ -0,0 +1,234 @@
+use crate::{
+    dedup, util::distance_2, ExtrapolationMode, Interpolator, InterpolatorError, Location, Point,
+};
+
+/// The position of a point projected on the line of the collinear sites.
+pub(crate) enum LinePosition {
+    /// The projected point is on the site.
+    Site(usize),
+    /// The projected point is between the sites, at the parameter (0.0-1.0) from the first site.
+    Between(usize, usize, f64),
+    /// The projected point is beyond the site at the end of the line.
+    Beyond(usize),
+}
+
+impl Interpolator {
+    /// Create a new Interpolator from a slice of points, checking that they can be triangulated.
+    ///
+    /// Returns an error instead of an interpolator which never interpolates:
+    ///  - `InterpolatorError::NonFiniteCoordinate` if a point has a NaN or infinite coordinate
+    ///  - `InterpolatorError::TooFewPoints` if there are fewer than three distinct points
+    ///  - `InterpolatorError::Collinear` if all the points are on a line
+    ///
+    /// Use `try_new_with_linear_fallback` to accept collinear points.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, InterpolatorError, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// assert!(Interpolator::try_new(&points).is_ok());
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    /// ];
+    /// assert!(matches!(
+    ///     Interpolator::try_new(&points),
+    ///     Err(InterpolatorError::Collinear)
+    /// ));
+    /// ```
+    pub fn try_new<P>(points: &[P]) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let (points, merged) = Self::validate_points(points, 3)?;
+        let interpolator = Self::from_merged_sites(points, merged, 0., Default::default());
+        if interpolator.triangles.is_empty() {
+            return Err(InterpolatorError::Collinear);
+        }
+        Ok(interpolator)
+    }
+
+    /// Create a new Interpolator from a slice of points, falling back to 1D linear interpolation if all the points are on a line.
+    ///
+    /// If the sites are collinear, the target point is projected on the line of the sites,
+    /// and the value is linearly interpolated between the two sites on both sides of the projected point.
+    /// The location of the projected point is reported as `Location::OnSite` or `Location::OnHullEdge`.
+    /// The projected points beyond the ends of the line are extrapolated with the site at the end unless the extrapolation mode is `ExtrapolationMode::None`.
+    /// The fallback is applied whenever the sites are collinear, including after `insert` and `remove`.
+    ///
+    /// Returns `InterpolatorError::NonFiniteCoordinate` if a point has a NaN or infinite coordinate,
+    /// and `InterpolatorError::TooFewPoints` if there are fewer than two distinct points.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 3.0, y: 3.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0];
+    ///
+    /// let interpolator = Interpolator::try_new_with_linear_fallback(&points).unwrap();
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 2.0, y: 2.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 1.5).abs() < 1e-6);
+    ///
+    /// // the point is projected on the line
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 1.0, y: 0.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn try_new_with_linear_fallback<P>(points: &[P]) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let (points, merged) = Self::validate_points(points, 2)?;
+        let mut interpolator = Self::from_merged_sites(points, merged, 0., Default::default());
+        interpolator.linear_fallback = true;
+        Ok(interpolator)
+    }
+
+    /// Check that the points are finite and there are at least `min_sites` distinct points,
+    /// and find the sites which the points are merged into.
+    pub(crate) fn validate_points<P>(
+        points: &[P],
+        min_sites: usize,
+    ) -> Result<(Vec<Point>, Vec<usize>), InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let points = points
+            .iter()
+            .map(|p| (*p).clone().into())
+            .collect::<Vec<Point>>();
+
+        if let Some(i) = points
+            .iter()
+            .position(|p| !p.x.is_finite() || !p.y.is_finite())
+        {
+            return Err(InterpolatorError::NonFiniteCoordinate(i));
+        }
+
+        let merged = dedup::merge_duplicates(&points, 0.);
+        if merged
+            .iter()
+            .enumerate()
+            .filter(|(i, site)| i == *site)
+            .count()
+            < min_sites
+        {
+            return Err(InterpolatorError::TooFewPoints);
+        }
+        Ok((points, merged))
+    }
+
+    /// Check if the sites are interpolated linearly because they are collinear.
+    pub(crate) fn is_linear(&self) -> bool {
+        self.linear_fallback && self.triangles.is_empty()
+    }
+
+    /// Project the point on the line of the collinear sites.
+    /// Returns None if there are no sites or the point has a NaN coordinate.
+    ///
+    /// The sites are scanned linearly, as the fallback is not expected to be used for a large number of sites.
+    pub(crate) fn project_on_line(&self, ptarget: &Point) -> Option<LinePosition> {
+        let mut sites =
+            (0..self.points.len()).filter(|i| !self.vacant[*i] && self.merged[*i] == *i);
+        let origin = sites.next()?;
+        let o = &self.points[origin];
+
+        // the direction of the line is given by the farthest site from the first one
+        let far = sites
+            .clone()
+            .max_by(|a, b| {
+                distance_2(o, &self.points[*a]).total_cmp(&distance_2(o, &self.points[*b]))
+            })
+            .unwrap_or(origin);
+        if far == origin {
+            return Some(if ptarget.x == o.x && ptarget.y == o.y {
+                LinePosition::Site(origin)
+            } else {
+                LinePosition::Beyond(origin)
+            });
+        }
+        let d = [self.points[far].x - o.x, self.points[far].y - o.y];
+        let project = |p: &Point| (p.x - o.x) * d[0] + (p.y - o.y) * d[1];
+
+        let tq = project(ptarget);
+        // the nearest sites on both sides of the projected point
+        let mut lower: Option<(usize, f64)> = None;
+        let mut upper: Option<(usize, f64)> = None;
+        for i in std::iter::once(origin).chain(sites) {
+            let t = project(&self.points[i]);
+            if t <= tq && lower.map_or(true, |(_, tl)| t > tl) {
+                lower = Some((i, t));
+            }
+            if t >= tq && upper.map_or(true, |(_, tu)| t < tu) {
+                upper = Some((i, t));
+            }
+        }
+
+        Some(match (lower, upper) {
+            (Some((a, ta)), Some((b, tb))) => {
+                if ta == tq {
+                    LinePosition::Site(a)
+                } else if tb == tq {
+                    LinePosition::Site(b)
+                } else {
+                    LinePosition::Between(a, b, (tq - ta) / (tb - ta))
+                }
+            }
+            (Some((i, _)), None) | (None, Some((i, _))) => LinePosition::Beyond(i),
+            // the projection is NaN
+            (None, None) => return None,
+        })
+    }
+
+    /// Find where the point projected on the line of the collinear sites is located.
+    pub(crate) fn locate_on_line(&self, ptarget: &Point) -> Location {
+        match self.project_on_line(ptarget) {
+            Some(LinePosition::Site(i)) => Location::OnSite(i),
+            Some(LinePosition::Between(a, b, _)) => Location::OnHullEdge(a, b),
+            _ => Location::Outside,
+        }
+    }
+
+    /// Calculate the weights of the collinear sites for the point.
+    pub(crate) fn perform_linear_interpolation(
+        &self,
+        ptarget: &Point,
+        apply_weight: &mut impl FnMut(usize, f64, [f64; 2], f64),
+    ) -> Location {
+        match self.project_on_line(ptarget) {
+            Some(LinePosition::Site(i)) => {
+                apply_weight(i, 1., [0., 0.], 1.);
+                Location::OnSite(i)
+            }
+            Some(LinePosition::Between(a, b, t)) => {
+                self.apply_edge_weights(a, b, t, apply_weight);
+                Location::OnHullEdge(a, b)
+            }
+            Some(LinePosition::Beyond(i)) => {
+                if self.extrapolation_mode != ExtrapolationMode::None {
+                    apply_weight(i, 1., [0., 0.], 1.);
+                }
+                Location::Outside
+            }
+            None => Location::Outside,
+        }
+    }
+}
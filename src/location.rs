@@ -0,0 +1,122 @@
+use crate::{
+    util::{next_harfedge, orient},
+    Interpolator, InterpolatorError, Lerpable, Point, QueryHint,
+};
+
+/// Represents where a point is located in the triangulation of the sites.
+///
+/// The natural neighbor coordinates are calculated exactly in each case:
+/// the weight of the site is 1 for a point on a site,
+/// and the weights are linear along the edge for a point on the convex hull.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    /// The point is strictly inside a triangle.
+    Inside,
+    /// The point is on the edge between two triangles. The indices of the sites at the ends of the edge are given.
+    OnEdge(usize, usize),
+    /// The point is on an edge of the convex hull. The indices of the sites at the ends of the edge are given.
+    OnHullEdge(usize, usize),
+    /// The point is on the site with the index.
+    OnSite(usize),
+    /// The point is outside the convex hull of the sites (or the domain, see `Interpolator::with_domain`).
+    Outside,
+}
+
+impl Interpolator {
+    /// Find where the point is located in the triangulation of the sites.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Location, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let interpolator = Interpolator::new(&points);
+    ///
+    /// assert_eq!(interpolator.locate(Point { x: 1.0, y: 1.0 }), Location::OnSite(2));
+    /// assert_eq!(interpolator.locate(Point { x: 2.0, y: 1.0 }), Location::Outside);
+    /// assert!(matches!(
+    ///     interpolator.locate(Point { x: 0.5, y: 0.0 }),
+    ///     Location::OnHullEdge(_, _)
+    /// ));
+    /// ```
+    pub fn locate<P>(&self, ptarget: P) -> Location
+    where
+        P: Into<Point>,
+    {
+        let ptarget = self.wrap_periodic(ptarget.into());
+        if !self.domain_contains(&ptarget) {
+            return Location::Outside;
+        }
+        if self.is_linear() {
+            return self.locate_on_line(&ptarget);
+        }
+        if let Some(it) = self.fit_in_triangle(&ptarget) {
+            self.site_location(self.location_in_triangle(&ptarget, it))
+        } else {
+            Location::Outside
+        }
+    }
+
+    /// Interpolate the value at the point, and report where the point is located in the triangulation.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Location, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    /// let interpolator = Interpolator::new(&points);
+    ///
+    /// let (value, location) = interpolator
+    ///     .interpolate_with_location(&values, Point { x: 1.0, y: 1.0 })
+    ///     .unwrap();
+    /// assert_eq!(value, Some(2.0));
+    /// assert_eq!(location, Location::OnSite(2));
+    /// ```
+    pub fn interpolate_with_location<P, V>(
+        &self,
+        values: &[V],
+        ptarget: P,
+    ) -> Result<(Option<V>, Location), InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+        V: Lerpable,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        self.interpolate_with_hint(values, ptarget.into(), &mut QueryHint::default())
+    }
+
+    /// Find where the point is located in the triangle `it`, which contains the point.
+    pub(crate) fn location_in_triangle(&self, ptarget: &Point, it: usize) -> Location {
+        for i in &self.triangles[it * 3..it * 3 + 3] {
+            let p = &self.points[*i];
+            if p.x == ptarget.x && p.y == ptarget.y {
+                return Location::OnSite(*i);
+            }
+        }
+        for e in it * 3..it * 3 + 3 {
+            let (a, b) = (self.triangles[e], self.triangles[next_harfedge(e)]);
+            if orient(&self.points[a], &self.points[b], ptarget) == 0. {
+                return if self.harfedges[e] < self.harfedges.len() {
+                    Location::OnEdge(a, b)
+                } else {
+                    Location::OnHullEdge(a, b)
+                };
+            }
+        }
+        Location::Inside
+    }
+}
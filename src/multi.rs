@@ -0,0 +1,69 @@
+use crate::{Interpolator, InterpolatorError, Lerpable, Point};
+
+impl Interpolator {
+    /// Interpolate the values of several fields at the point at once.
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
+    ///
+    /// Each field is a slice of the values of the sites, and the interpolated values are returned in the same order as the fields.
+    /// The natural neighbors and their weights are calculated only once for all the fields.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let temperature = [10.0f64, 12.0, 14.0, 12.0];
+    /// let humidity = [0.5f64, 0.6, 0.7, 0.6];
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let values = interpolator
+    ///     .interpolate_multi(&[&temperature, &humidity], Point { x: 0.5, y: 0.5 })
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert!((values[0] - 12.0).abs() < 1e-6);
+    /// assert!((values[1] - 0.6).abs() < 1e-6);
+    /// ```
+    pub fn interpolate_multi<P, V>(
+        &self,
+        fields: &[&[V]],
+        ptarget: P,
+    ) -> Result<Option<Vec<V>>, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+        V: Lerpable,
+    {
+        if fields
+            .iter()
+            .any(|values| self.site_count() != values.len())
+        {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let mut weights = Vec::new();
+        self.perform_interpoation::<P>(ptarget, &mut |i, weight, _, _| {
+            weights.push((i, weight));
+        })?;
+
+        if weights.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(
+            fields
+                .iter()
+                .map(|values| {
+                    let terms = weights
+                        .iter()
+                        .map(|(i, w)| (&values[*i], *w))
+                        .collect::<Vec<_>>();
+                    V::blend(&terms)
+                })
+                .collect(),
+        ))
+    }
+}
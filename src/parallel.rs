@@ -0,0 +1,75 @@
+use rayon::prelude::*;
+
+use crate::{GridSpec, Interpolator, InterpolatorError, Lerpable, Point, QueryHint};
+
+impl Interpolator {
+    /// Interpolate the values at the points in parallel.
+    ///
+    /// The result is the same as calling `interpolate` for each point in order.
+    /// Available with the `rayon` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let targets = [Point { x: 0.5, y: 0.5 }, Point { x: 2.0, y: 2.0 }];
+    ///
+    /// let result = interpolator.par_interpolate_many(&values, &targets).unwrap();
+    /// assert!((result[0].unwrap() - 1.0).abs() < 1e-6);
+    /// assert!(result[1].is_none());
+    /// ```
+    pub fn par_interpolate_many<P, V>(
+        &self,
+        values: &[V],
+        ptargets: &[P],
+    ) -> Result<Vec<Option<V>>, InterpolatorError>
+    where
+        P: Into<Point> + Clone + Sync,
+        V: Lerpable + Send + Sync,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        ptargets
+            .par_iter()
+            .map(|p| {
+                self.interpolate_with_hint(values, p.clone().into(), &mut QueryHint::default())
+                    .map(|(value, _)| value)
+            })
+            .collect()
+    }
+
+    /// Interpolate the values at all the points of the grid in parallel.
+    ///
+    /// The rows of the grid are processed in parallel,
+    /// and the result is the same as `interpolate_grid`.
+    /// Available with the `rayon` feature.
+    pub fn par_interpolate_grid<V>(
+        &self,
+        values: &[V],
+        grid: GridSpec,
+    ) -> Result<Vec<Option<V>>, InterpolatorError>
+    where
+        V: Lerpable + Send + Sync,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let rows = (0..grid.ny)
+            .into_par_iter()
+            .map(|iy| self.interpolate_grid_row(values, &grid, iy))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows.into_iter().flatten().collect())
+    }
+}
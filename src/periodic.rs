@@ -0,0 +1,279 @@
+use crate::{
+    dedup::merge_duplicates, util::distance_2, Interpolator, InterpolatorError, Location, Point,
+};
+
+/// The periods of the sites in x and y, for the periodic (toroidal) domain.
+#[derive(Debug, Clone)]
+pub(crate) struct Periodicity {
+    period: [f64; 2],
+    // the minimum corner of the sites, where the domain of one period starts
+    origin: Point,
+    // the number of the sites without the ghost copies
+    sites: usize,
+}
+
+// The offsets of the copies of the sites, in the unit of the period.
+// The first one is the sites themselves and the others are the ghost copies around them.
+pub(crate) static TILES: [[f64; 2]; 9] = [
+    [0., 0.],
+    [-1., -1.],
+    [0., -1.],
+    [1., -1.],
+    [-1., 0.],
+    [1., 0.],
+    [-1., 1.],
+    [0., 1.],
+    [1., 1.],
+];
+
+impl Periodicity {
+    /// Create the periodicity of the sites (without the ghost copies).
+    pub(crate) fn new(period: [f64; 2], sites: &[Point]) -> Self {
+        Self {
+            period,
+            origin: Point {
+                x: sites.iter().map(|p| p.x).fold(f64::INFINITY, f64::min),
+                y: sites.iter().map(|p| p.y).fold(f64::INFINITY, f64::min),
+            },
+            sites: sites.len(),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    pub(crate) fn period(&self) -> [f64; 2] {
+        self.period
+    }
+}
+
+impl Interpolator {
+    /// Create a new Interpolator for the sites repeated with the periods in x and y.
+    ///
+    /// The sites are triangulated together with their ghost copies shifted by the periods,
+    /// so the points near the edges of the domain are interpolated with the neighbors across the edges.
+    /// The points outside the domain are wrapped into it before the interpolation.
+    ///
+    /// The sites must be in a domain of one period, such as `[0, period_x) x [0, period_y)`,
+    /// and the periods must be finite and positive. Use `try_new_periodic` to check them.
+    /// The indices of the ghost copies are mapped back to the sites,
+    /// so the values passed to `interpolate` are given only for the sites,
+    /// and `query_weights` returns the weight of each site aggregated over its copies.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 0.0 },
+    ///     Point { x: 0.0, y: 2.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 0.0, 1.0];
+    ///
+    /// let interpolator = Interpolator::new_periodic(&points, 4.0, 4.0);
+    ///
+    /// // the sites at x = 0.0 are also at x = 4.0
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 3.0, y: 0.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 0.5).abs() < 1e-6);
+    ///
+    /// // the point is wrapped into the domain
+    /// let wrapped = interpolator
+    ///     .interpolate(&values, Point { x: -1.0, y: 4.0 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((wrapped - 0.5).abs() < 1e-6);
+    /// ```
+    pub fn new_periodic<P>(points: &[P], period_x: f64, period_y: f64) -> Self
+    where
+        P: Into<Point> + Clone,
+    {
+        let sites = points
+            .iter()
+            .map(|p| (*p).clone().into())
+            .collect::<Vec<Point>>();
+
+        let mut interpolator = Self::new::<Point>(&[]);
+        interpolator.periodicity = Some(Periodicity::new([period_x, period_y], &[]));
+        let vacant = vec![false; sites.len()];
+        interpolator.rebuild_periodic(sites, vacant);
+        interpolator
+    }
+
+    /// Create a new Interpolator for the sites repeated with the periods in x and y, checking the periods and the sites.
+    ///
+    /// Returns an error instead of an interpolator which interpolates wrongly:
+    ///  - `InterpolatorError::InvalidPeriod` if a period is not finite and positive
+    ///  - `InterpolatorError::NonFiniteCoordinate` if a point has a NaN or infinite coordinate
+    ///  - `InterpolatorError::TooFewPoints` if no point is given
+    ///  - `InterpolatorError::SiteOutsidePeriod` if the sites are not in a domain of one period,
+    ///    which starts from the minimum coordinates of the sites
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, InterpolatorError, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 8.0, y: 0.0 },
+    ///     Point { x: 0.0, y: 2.0 },
+    /// ];
+    /// assert!(Interpolator::try_new_periodic(&points, 10.0, 10.0).is_ok());
+    ///
+    /// assert!(matches!(
+    ///     Interpolator::try_new_periodic(&points, 0.0, 10.0),
+    ///     Err(InterpolatorError::InvalidPeriod(_))
+    /// ));
+    /// assert!(matches!(
+    ///     Interpolator::try_new_periodic(&points, 5.0, 10.0),
+    ///     Err(InterpolatorError::SiteOutsidePeriod(1))
+    /// ));
+    /// ```
+    pub fn try_new_periodic<P>(
+        points: &[P],
+        period_x: f64,
+        period_y: f64,
+    ) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        if let Some(period) = [period_x, period_y]
+            .into_iter()
+            .find(|period| !period.is_finite() || *period <= 0.)
+        {
+            return Err(InterpolatorError::InvalidPeriod(period));
+        }
+
+        let (sites, _) = Self::validate_points(points, 1)?;
+        let origin = Periodicity::new([period_x, period_y], &sites).origin;
+        if let Some(i) = sites
+            .iter()
+            .position(|p| p.x - origin.x >= period_x || p.y - origin.y >= period_y)
+        {
+            return Err(InterpolatorError::SiteOutsidePeriod(i));
+        }
+
+        Ok(Self::new_periodic(&sites, period_x, period_y))
+    }
+
+    /// Triangulate the sites and their ghost copies again.
+    /// The removed sites are given as `vacant`.
+    fn rebuild_periodic(&mut self, sites: Vec<Point>, vacant: Vec<bool>) {
+        let period = if let Some(periodicity) = &self.periodicity {
+            periodicity.period
+        } else {
+            return;
+        };
+        self.periodicity = Some(Periodicity::new(period, &sites));
+
+        self.points = TILES
+            .iter()
+            .flat_map(|[tx, ty]| {
+                sites.iter().map(move |p| Point {
+                    x: p.x + tx * period[0],
+                    y: p.y + ty * period[1],
+                })
+            })
+            .collect();
+        self.vacant = vacant.repeat(TILES.len());
+        self.merged = (0..self.points.len()).collect();
+        self.groups.clear();
+        for (i, site) in merge_duplicates(&self.points, 0.).into_iter().enumerate() {
+            if i != site && !self.vacant[i] && !self.vacant[site] {
+                self.merge_into(i, site);
+            }
+        }
+        if let Some(domain) = &self.domain {
+            self.in_domain = self.points.iter().map(|p| domain.contains(p)).collect();
+        }
+        self.retriangulate();
+    }
+
+    /// Insert a new site into the periodic interpolator and return its index.
+    /// The site is wrapped into the domain of one period, and the whole triangulation is rebuilt.
+    pub(crate) fn insert_periodic(&mut self, point: Point) -> usize {
+        let n = self.site_count();
+        let point = if n > 0 {
+            self.wrap_periodic(point)
+        } else {
+            point
+        };
+        let mut sites = self.points[..n].to_vec();
+        let mut vacant = self.vacant[..n].to_vec();
+        sites.push(point);
+        vacant.push(false);
+        self.rebuild_periodic(sites, vacant);
+        n
+    }
+
+    /// Remove the site from the periodic interpolator.
+    /// The whole triangulation is rebuilt.
+    pub(crate) fn remove_periodic(&mut self, index: usize) -> Result<(), InterpolatorError> {
+        let n = self.site_count();
+        if index >= n || self.is_vacant(index) {
+            return Err(InterpolatorError::InvalidSiteIndex(index));
+        }
+        let sites = self.points[..n].to_vec();
+        let mut vacant = self.vacant[..n].to_vec();
+        vacant[index] = true;
+        self.rebuild_periodic(sites, vacant);
+        Ok(())
+    }
+
+    /// Check if the interpolator is in the periodic mode (see `new_periodic`).
+    pub(crate) fn is_periodic(&self) -> bool {
+        self.periodicity.is_some()
+    }
+
+    /// The number of the sites, which is the number of the values to be interpolated.
+    pub(crate) fn site_count(&self) -> usize {
+        self.periodicity
+            .as_ref()
+            .map_or(self.points.len(), |periodicity| periodicity.sites)
+    }
+
+    /// The site of the point in the triangulation, which is different only for the ghost copies.
+    pub(crate) fn site_of(&self, i: usize) -> usize {
+        self.periodicity
+            .as_ref()
+            .map_or(i, |periodicity| i % periodicity.sites)
+    }
+
+    /// Map the sites of the location from the ghost copies.
+    pub(crate) fn site_location(&self, location: Location) -> Location {
+        match location {
+            Location::OnSite(i) => Location::OnSite(self.site_of(i)),
+            Location::OnEdge(a, b) => Location::OnEdge(self.site_of(a), self.site_of(b)),
+            Location::OnHullEdge(a, b) => Location::OnHullEdge(self.site_of(a), self.site_of(b)),
+            _ => location,
+        }
+    }
+
+    /// Wrap the point into the domain of one period.
+    pub(crate) fn wrap_periodic(&self, p: Point) -> Point {
+        if let Some(periodicity) = &self.periodicity {
+            let (origin, period) = (&periodicity.origin, periodicity.period);
+            Point {
+                x: origin.x + (p.x - origin.x).rem_euclid(period[0]),
+                y: origin.y + (p.y - origin.y).rem_euclid(period[1]),
+            }
+        } else {
+            p
+        }
+    }
+
+    /// The position of the site, or its copy nearest to the point in the periodic mode.
+    pub(crate) fn site_position(&self, i: usize, near: &Point) -> Point {
+        if let Some(periodicity) = &self.periodicity {
+            (0..TILES.len())
+                .map(|k| &self.points[k * periodicity.sites + i])
+                .min_by(|a, b| distance_2(a, near).total_cmp(&distance_2(b, near)))
+                .unwrap()
+                .clone()
+        } else {
+            self.points[i].clone()
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::Point3D;
+
+/// Convex polyhedron to be clipped by the bisectors between sites.
+///
+/// This is used to calculate the volume of (a part of) the voronoi cell of a point in 3D.
+/// Each face is a convex polygon, and the faces are not shared between each other.
+#[derive(Debug, Clone)]
+pub(crate) struct ConvexPolyhedron {
+    faces: Vec<Vec<Point3D>>,
+}
+
+impl ConvexPolyhedron {
+    /// Create an axis-aligned box from the corners.
+    pub fn aabb(min: &Point3D, max: &Point3D) -> Self {
+        let corner = |i: usize| Point3D {
+            x: if i & 1 == 0 { min.x } else { max.x },
+            y: if i & 2 == 0 { min.y } else { max.y },
+            z: if i & 4 == 0 { min.z } else { max.z },
+        };
+        let faces = [
+            [0, 2, 3, 1],
+            [4, 5, 7, 6],
+            [0, 1, 5, 4],
+            [2, 6, 7, 3],
+            [0, 4, 6, 2],
+            [1, 3, 7, 5],
+        ]
+        .iter()
+        .map(|face| face.iter().map(|i| corner(*i)).collect())
+        .collect();
+        Self { faces }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.faces.len() < 4
+    }
+
+    /// Keep the part of the polyhedron which is closer to `site` than to `other`.
+    pub fn clip_bisector(&mut self, site: &Point3D, other: &Point3D) {
+        if self.is_empty() {
+            return;
+        }
+
+        let m = [
+            (site.x + other.x) / 2.,
+            (site.y + other.y) / 2.,
+            (site.z + other.z) / 2.,
+        ];
+        let n = [other.x - site.x, other.y - site.y, other.z - site.z];
+
+        // signed distance (scaled) from the bisector. negative values are on the side of `site`.
+        let side = |p: &Point3D| (p.x - m[0]) * n[0] + (p.y - m[1]) * n[1] + (p.z - m[2]) * n[2];
+
+        if self.faces.iter().flatten().all(|p| side(p) <= 0.) {
+            return;
+        }
+
+        // the vertices of the new face on the bisector
+        let mut cap = Vec::new();
+        let mut faces = Vec::with_capacity(self.faces.len() + 1);
+        for face in &self.faces {
+            let mut clipped = Vec::with_capacity(face.len() + 1);
+            for (i, current) in face.iter().enumerate() {
+                let next = &face[(i + 1) % face.len()];
+                let (sc, sn) = (side(current), side(next));
+                if sc <= 0. {
+                    clipped.push(current.clone());
+                    if sc == 0. {
+                        cap.push(current.clone());
+                    }
+                }
+                if (sc < 0. && sn > 0.) || (sc > 0. && sn < 0.) {
+                    let t = sc / (sc - sn);
+                    let p = Point3D {
+                        x: current.x + (next.x - current.x) * t,
+                        y: current.y + (next.y - current.y) * t,
+                        z: current.z + (next.z - current.z) * t,
+                    };
+                    clipped.push(p.clone());
+                    cap.push(p);
+                }
+            }
+            if clipped.len() >= 3 {
+                faces.push(clipped);
+            }
+        }
+
+        if cap.len() >= 3 {
+            // sort the vertices of the new face around its center
+            let c = centroid(&cap);
+            let u = normalize(cross(n, sub(&cap[0], &c)));
+            let v = cross(n, u);
+            let angle = |p: &Point3D| {
+                let d = sub(p, &c);
+                dot(d, v).atan2(dot(d, u))
+            };
+            cap.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+            faces.push(cap);
+        }
+        self.faces = faces;
+    }
+
+    /// The volume of the polyhedron.
+    pub fn volume(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let c = centroid(&self.faces.iter().flatten().cloned().collect::<Vec<_>>());
+        let mut volume = 0.;
+        for face in &self.faces {
+            let a = sub(&face[0], &c);
+            for i in 1..face.len() - 1 {
+                let (b, d) = (sub(&face[i], &c), sub(&face[i + 1], &c));
+                volume += dot(a, cross(b, d)).abs();
+            }
+        }
+        volume / 6.
+    }
+}
+
+fn centroid(points: &[Point3D]) -> Point3D {
+    let n = points.len() as f64;
+    Point3D {
+        x: points.iter().map(|p| p.x).sum::<f64>() / n,
+        y: points.iter().map(|p| p.y).sum::<f64>() / n,
+        z: points.iter().map(|p| p.z).sum::<f64>() / n,
+    }
+}
+
+fn sub(a: &Point3D, b: &Point3D) -> [f64; 3] {
+    [a.x - b.x, a.y - b.y, a.z - b.z]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn normalize(a: [f64; 3]) -> [f64; 3] {
+    let l = dot(a, a).sqrt();
+    [a[0] / l, a[1] / l, a[2] / l]
+}
@@ -1,17 +1,16 @@
 use rstar::{PointDistance, RTreeObject, AABB};
 
-use crate::Point;
+use crate::{
+    util::{orient, orient3d},
+    Point, Point3D,
+};
 /// Triangle object to be inserted into an RTree.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Triangle {
     itriangle: usize,
     aabb: AABB<[f64; 2]>,
 }
 
-// The epsiron value for the Triangle.
-// This is for querying 'all' triangles in a given AABB or point.
-static EPS_TRIANGLE: f64 = f64::EPSILON;
-
 impl Triangle {
     pub fn itriangle(&self) -> usize {
         self.itriangle
@@ -37,10 +36,9 @@ impl Triangle {
             points[triangle[2]].y,
         );
 
-        let eps = EPS_TRIANGLE;
         Self {
             itriangle: t,
-            aabb: AABB::from_corners([min_x - eps, min_y - eps], [max_x + eps, max_y + eps]),
+            aabb: AABB::from_corners([min_x, min_y], [max_x, max_y]),
         }
     }
 
@@ -54,16 +52,11 @@ impl Triangle {
         let p2 = &points[triangle[1]];
         let p3 = &points[triangle[2]];
 
-        let area2 = -p2.y * p3.x + p1.y * (-p2.x + p3.x) + p1.x * (p2.y - p3.y) + p2.x * p3.y;
-
-        let s = 1.0 / area2
-            * (p1.y * p3.x - p1.x * p3.y + (p3.y - p1.y) * point.x + (p1.x - p3.x) * point.y);
-        let t = 1.0 / area2
-            * (p1.x * p2.y - p1.y * p2.x + (p1.y - p2.y) * point.x + (p2.x - p1.x) * point.y);
-        let u = 1.0 - s - t;
-
-        let eps = EPS_TRIANGLE;
-        s >= -eps && t >= -eps && u >= -eps
+        // the point is inside (or on the edges) if it is not on the opposite side of any edge
+        let o1 = orient(p1, p2, point);
+        let o2 = orient(p2, p3, point);
+        let o3 = orient(p3, p1, point);
+        (o1 <= 0. && o2 <= 0. && o3 <= 0.) || (o1 >= 0. && o2 >= 0. && o3 >= 0.)
     }
 }
 
@@ -81,3 +74,62 @@ impl PointDistance for Triangle {
         (min[0] - point[0]).powi(2) + (min[1] - point[1]).powi(2)
     }
 }
+
+/// Tetrahedron object to be inserted into an RTree.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Tetrahedron {
+    itetrahedron: usize,
+    aabb: AABB<[f64; 3]>,
+}
+
+impl Tetrahedron {
+    pub fn itetrahedron(&self) -> usize {
+        self.itetrahedron
+    }
+
+    pub fn from_tetrahedron(points: &[Point3D], tetrahedra: &[[usize; 4]], t: usize) -> Self {
+        let vertices = tetrahedra[t].map(|i| &points[i]);
+        let min = |f: fn(&Point3D) -> f64| vertices.iter().map(|p| f(p)).fold(f64::MAX, f64::min);
+        let max = |f: fn(&Point3D) -> f64| vertices.iter().map(|p| f(p)).fold(f64::MIN, f64::max);
+
+        Self {
+            itetrahedron: t,
+            aabb: AABB::from_corners(
+                [min(|p| p.x), min(|p| p.y), min(|p| p.z)],
+                [max(|p| p.x), max(|p| p.y), max(|p| p.z)],
+            ),
+        }
+    }
+
+    pub fn point_in_tetrahedron(
+        &self,
+        points: &[Point3D],
+        tetrahedra: &[[usize; 4]],
+        point: &Point3D,
+    ) -> bool {
+        let vertices = tetrahedra[self.itetrahedron].map(|i| &points[i]);
+        let o = orient3d(vertices[0], vertices[1], vertices[2], vertices[3]);
+
+        // the point is inside (or on the faces) if it is not on the opposite side of any face
+        (0..4).all(|k| {
+            let mut replaced = vertices;
+            replaced[k] = point;
+            orient3d(replaced[0], replaced[1], replaced[2], replaced[3]) * o >= 0.
+        })
+    }
+}
+
+impl RTreeObject for Tetrahedron {
+    type Envelope = AABB<[f64; 3]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.aabb
+    }
+}
+
+impl PointDistance for Tetrahedron {
+    fn distance_2(&self, point: &[f64; 3]) -> f64 {
+        let min = self.aabb.min_point(point);
+        (min[0] - point[0]).powi(2) + (min[1] - point[1]).powi(2) + (min[2] - point[2]).powi(2)
+    }
+}
@@ -0,0 +1,214 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    periodic::{Periodicity, TILES},
+    CoordinateKind, Domain, ExtrapolationMode, Interpolator, InterpolatorError, MergePolicy, Point,
+};
+
+/// The serialized form of the interpolator.
+///
+/// The triangulation is stored as it is, and the RTree is rebuilt from it on load.
+#[derive(Serialize, Deserialize)]
+struct InterpolatorData {
+    points: Vec<[f64; 2]>,
+    vacant: Vec<bool>,
+    merged: Vec<usize>,
+    merge_tolerance: f64,
+    merge_policy: MergePolicy,
+    triangles: Vec<usize>,
+    harfedges: Vec<usize>,
+    breaklines: Vec<(usize, usize)>,
+    degree_limitation: usize,
+    extrapolation_mode: ExtrapolationMode,
+    coordinate_kind: CoordinateKind,
+    domain: Option<DomainData>,
+    linear_fallback: bool,
+    period: Option<[f64; 2]>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DomainData {
+    outer: Vec<[f64; 2]>,
+    holes: Vec<Vec<[f64; 2]>>,
+}
+
+fn to_array(p: &Point) -> [f64; 2] {
+    [p.x, p.y]
+}
+
+fn to_point(p: &[f64; 2]) -> Point {
+    Point { x: p[0], y: p[1] }
+}
+
+/// Serialize the interpolator with its triangulation, so it can be loaded without triangulating the sites again.
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Interpolator, Point};
+///
+/// let points = [
+///     Point { x: 0.0, y: 0.0 },
+///     Point { x: 1.0, y: 0.0 },
+///     Point { x: 1.0, y: 1.0 },
+///     Point { x: 0.0, y: 1.0 },
+/// ];
+/// let values = [0.0f64, 1.0, 2.0, 1.0];
+///
+/// let interpolator = Interpolator::new(&points);
+/// let json = serde_json::to_string(&interpolator).unwrap();
+/// let loaded: Interpolator = serde_json::from_str(&json).unwrap();
+///
+/// let value = loaded
+///     .interpolate(&values, Point { x: 0.5, y: 0.5 })
+///     .unwrap()
+///     .unwrap();
+/// assert!((value - 1.0).abs() < 1e-6);
+/// ```
+impl Serialize for Interpolator {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        InterpolatorData {
+            points: self.points.iter().map(to_array).collect(),
+            vacant: self.vacant.clone(),
+            merged: self.merged.clone(),
+            merge_tolerance: self.merge_tolerance,
+            merge_policy: self.merge_policy,
+            triangles: self.triangles.clone(),
+            harfedges: self.harfedges.clone(),
+            breaklines: self.breaklines(),
+            degree_limitation: self.degree_limitation,
+            extrapolation_mode: self.extrapolation_mode,
+            coordinate_kind: self.coordinate_kind,
+            domain: self.domain.as_ref().map(|domain| {
+                let (outer, holes) = domain.rings();
+                DomainData {
+                    outer: outer.iter().map(to_array).collect(),
+                    holes: holes
+                        .iter()
+                        .map(|hole| hole.iter().map(to_array).collect())
+                        .collect(),
+                }
+            }),
+            linear_fallback: self.linear_fallback,
+            period: self.periodicity.as_ref().map(|p| p.period()),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialize the interpolator, validating its triangulation and rebuilding the RTree.
+///
+/// The triangulation is rejected if it is inconsistent, has a triangle which is not clockwise,
+/// or refers to a removed or merged site.
+/// So are the non-finite coordinates, the sites merged into a removed or merged site,
+/// the degree limitation of zero and the periods which are not finite and positive.
+impl<'de> Deserialize<'de> for Interpolator {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let data = InterpolatorData::deserialize(deserializer)?;
+
+        let n = data.points.len();
+        if data.vacant.len() != n || data.merged.len() != n {
+            return Err(D::Error::custom(
+                "The number of points and the states of the sites are not the same.",
+            ));
+        }
+        if let Some(i) = data
+            .merged
+            .iter()
+            .chain(data.breaklines.iter().flat_map(|(a, b)| [a, b]))
+            .find(|i| **i >= n)
+        {
+            return Err(D::Error::custom(format!(
+                "The site ({}) does not exist.",
+                i
+            )));
+        }
+        if let Some(i) =
+            (0..n).find(|i| !data.vacant[*i] && data.points[*i].iter().any(|c| !c.is_finite()))
+        {
+            return Err(D::Error::custom(InterpolatorError::NonFiniteCoordinate(i)));
+        }
+        // the sites are merged directly into the sites which are neither removed nor merged
+        if let Some(i) = (0..n).find(|i| {
+            let site = data.merged[*i];
+            !data.vacant[*i] && site != *i && (data.vacant[site] || data.merged[site] != site)
+        }) {
+            return Err(D::Error::custom(format!(
+                "The site ({}) is merged into the site ({}) which is removed or merged.",
+                i, data.merged[i]
+            )));
+        }
+        if data.degree_limitation == 0 {
+            return Err(D::Error::custom("The degree limitation must be positive."));
+        }
+        if let Some(period) = data
+            .period
+            .iter()
+            .flatten()
+            .find(|period| !period.is_finite() || **period <= 0.)
+        {
+            return Err(D::Error::custom(InterpolatorError::InvalidPeriod(*period)));
+        }
+        if data.period.is_some() && n % TILES.len() != 0 {
+            return Err(D::Error::custom(
+                "The number of points does not match the ghost copies of the periodic mode.",
+            ));
+        }
+
+        let points = data.points.iter().map(to_point).collect::<Vec<_>>();
+        let mut interpolator = Self::new::<Point>(&[]);
+        interpolator.periodicity = data
+            .period
+            .map(|period| Periodicity::new(period, &points[..n / TILES.len()]));
+        if let Some(domain) = data.domain {
+            let outer = domain.outer.iter().map(to_point).collect::<Vec<_>>();
+            let domain = domain
+                .holes
+                .iter()
+                .fold(Domain::new(&outer), |domain, hole| {
+                    domain.with_hole(&hole.iter().map(to_point).collect::<Vec<_>>())
+                });
+            interpolator.in_domain = points.iter().map(|p| domain.contains(p)).collect();
+            interpolator.domain = Some(domain);
+        }
+        interpolator.points = points;
+        interpolator.vacant = data.vacant;
+        interpolator.merged = (0..n).collect();
+        for (i, site) in data.merged.iter().enumerate() {
+            if i != *site && !interpolator.vacant[i] {
+                interpolator.merge_into(i, *site);
+            }
+        }
+        interpolator.merge_tolerance = data.merge_tolerance;
+        interpolator.merge_policy = data.merge_policy;
+        interpolator.triangles = data.triangles;
+        interpolator.harfedges = data.harfedges;
+        interpolator.breaklines = data.breaklines.into_iter().collect();
+        interpolator.degree_limitation = data.degree_limitation;
+        interpolator.extrapolation_mode = data.extrapolation_mode;
+        interpolator.coordinate_kind = data.coordinate_kind;
+        interpolator.linear_fallback = data.linear_fallback;
+
+        interpolator
+            .validate_triangulation()
+            .and_then(|_| interpolator.validate_orientation())
+            .map_err(D::Error::custom)?;
+        if let Some(i) = interpolator
+            .triangles
+            .iter()
+            .find(|i| interpolator.vacant[**i] || interpolator.merged[**i] != **i)
+        {
+            return Err(D::Error::custom(format!(
+                "The triangulation refers to the site ({}) which is removed or merged.",
+                i
+            )));
+        }
+        interpolator.rebuild_tree();
+        Ok(interpolator)
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{CoordinateKind, Interpolator, InterpolatorError, Point};
+
+impl Interpolator {
+    /// Interpolate the value at the point with a C1 continuous interpolant.
+    ///
+    /// The natural neighbor coordinates are blended with the gradients of the sites
+    /// by the cubic Bézier form proposed by G. Farin ('Surfaces over Dirichlet tessellations', 1990).
+    /// Unlike `interpolate`, the surface is smooth at the sites,
+    /// and quadratic functions are reproduced exactly when the exact gradients are given.
+    /// The interpolant is built on Sibson coordinates whatever `CoordinateKind` is set,
+    /// because Laplace coordinates do not make it C1 continuous.
+    ///
+    /// `gradients` is the list of the gradients (df/dx, df/dy) of the sites.
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 0.0 },
+    ///     Point { x: 2.0, y: 2.0 },
+    ///     Point { x: 0.0, y: 2.0 },
+    ///     Point { x: 0.8, y: 1.1 },
+    /// ];
+    ///
+    /// // f(x, y) = x * x + y
+    /// let values = points.iter().map(|p| p.x * p.x + p.y).collect::<Vec<_>>();
+    /// let gradients = points.iter().map(|p| [2.0 * p.x, 1.0]).collect::<Vec<_>>();
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let value = interpolator
+    ///     .interpolate_c1(&values, &gradients, Point { x: 1.2, y: 0.7 })
+    ///     .unwrap()
+    ///     .unwrap();
+    ///
+    /// assert!((value - (1.2 * 1.2 + 0.7)).abs() < 1e-6);
+    /// ```
+    pub fn interpolate_c1<P>(
+        &self,
+        values: &[f64],
+        gradients: &[[f64; 2]],
+        ptarget: P,
+    ) -> Result<Option<f64>, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+        if self.site_count() != gradients.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndGradients);
+        }
+
+        let ptarget = self.wrap_periodic(ptarget.into());
+        let weights = if let Some(weights) =
+            self.query_weights_of_kind(ptarget.clone(), CoordinateKind::Sibson)?
+        {
+            weights
+        } else {
+            return Ok(None);
+        };
+
+        // The control points of the cubic Bézier form.
+        // b_iij = z_i + g_i * (x_j - x_i) / 3
+        let n = weights.len();
+        let bezier_iij = |i: usize, j: usize| -> f64 {
+            let (si, sj) = (weights[i].0, weights[j].0);
+            let (pi, pj) = (
+                self.site_position(si, &ptarget),
+                self.site_position(sj, &ptarget),
+            );
+            values[si] + (gradients[si][0] * (pj.x - pi.x) + gradients[si][1] * (pj.y - pi.y)) / 3.
+        };
+        let mut b = vec![0.; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                b[i * n + j] = if i == j {
+                    values[weights[i].0]
+                } else {
+                    bezier_iij(i, j)
+                };
+            }
+        }
+
+        let mut value = 0.;
+        for i in 0..n {
+            let ui = weights[i].1;
+            value += b[i * n + i] * ui * ui * ui;
+            for j in 0..n {
+                if i != j {
+                    value += 3. * b[i * n + j] * ui * ui * weights[j].1;
+                }
+            }
+        }
+        for i in 0..n {
+            for j in (i + 1)..n {
+                for k in (j + 1)..n {
+                    // b_ijk is chosen to reproduce quadratic functions.
+                    let bijk = (b[i * n + j]
+                        + b[i * n + k]
+                        + b[j * n + i]
+                        + b[j * n + k]
+                        + b[k * n + i]
+                        + b[k * n + j])
+                        / 4.
+                        - (b[i * n + i] + b[j * n + j] + b[k * n + k]) / 6.;
+                    value += 6. * bijk * weights[i].1 * weights[j].1 * weights[k].1;
+                }
+            }
+        }
+
+        Ok(Some(value))
+    }
+}
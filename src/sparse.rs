@@ -0,0 +1,129 @@
+use crate::{Interpolator, InterpolatorError, Point};
+
+/// The sparse matrix of the weights of the sites for the fixed query points, in the CSR (compressed sparse row) format.
+///
+/// Each row corresponds to a query point, and each column corresponds to a site.
+/// The row of the query point outside the triangulation is empty.
+/// Created by `Interpolator::weight_matrix`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseMatrix {
+    columns: usize,
+    // the elements of the row i are in row_offsets[i]..row_offsets[i + 1]
+    row_offsets: Vec<usize>,
+    column_indices: Vec<usize>,
+    weights: Vec<f64>,
+}
+
+impl SparseMatrix {
+    /// The number of the rows (the query points).
+    pub fn rows(&self) -> usize {
+        self.row_offsets.len() - 1
+    }
+
+    /// The number of the columns (the sites).
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// The number of the non-zero elements.
+    pub fn nnz(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// The sites and their weights in the row, which are the same as the result of `query_weights`.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let range = self.row_offsets[i]..self.row_offsets[i + 1];
+        self.column_indices[range.clone()]
+            .iter()
+            .copied()
+            .zip(self.weights[range].iter().copied())
+    }
+
+    /// Multiply the matrix by the values of the sites, to interpolate the values at all the query points.
+    /// None is returned for the query points outside the triangulation.
+    pub fn apply(&self, values: &[f64]) -> Result<Vec<Option<f64>>, InterpolatorError> {
+        if self.columns != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        Ok(self
+            .row_offsets
+            .windows(2)
+            .map(|range| {
+                if range[0] == range[1] {
+                    return None;
+                }
+                Some(
+                    self.column_indices[range[0]..range[1]]
+                        .iter()
+                        .zip(&self.weights[range[0]..range[1]])
+                        .map(|(i, w)| values[*i] * w)
+                        .sum(),
+                )
+            })
+            .collect())
+    }
+}
+
+impl Interpolator {
+    /// Calculate the weights of the sites for the query points as a sparse matrix.
+    ///
+    /// The weights depend only on the positions of the sites and the query points,
+    /// so the values at the same query points are interpolated repeatedly by `SparseMatrix::apply`
+    /// without searching the natural neighbors again.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let queries = [
+    ///     Point { x: 0.5, y: 0.5 },
+    ///     Point { x: 1.0, y: 0.5 },
+    ///     Point { x: 2.0, y: 0.5 },
+    /// ];
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let matrix = interpolator.weight_matrix(&queries).unwrap();
+    /// assert_eq!(matrix.rows(), 3);
+    /// assert_eq!(matrix.columns(), 4);
+    ///
+    /// for values in [[0.0f64, 1.0, 2.0, 1.0], [1.0, 1.0, 3.0, 3.0]] {
+    ///     let interpolated = matrix.apply(&values).unwrap();
+    ///     for (query, value) in queries.iter().zip(interpolated) {
+    ///         assert_eq!(value, interpolator.interpolate(&values, query.clone()).unwrap());
+    ///     }
+    /// }
+    /// ```
+    pub fn weight_matrix<P>(&self, queries: &[P]) -> Result<SparseMatrix, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let mut row_offsets = Vec::with_capacity(queries.len() + 1);
+        let mut column_indices = Vec::new();
+        let mut weights = Vec::new();
+
+        row_offsets.push(0);
+        for query in queries {
+            if let Some(row) = self.query_weights(query.clone())? {
+                for (i, w) in row {
+                    column_indices.push(i);
+                    weights.push(w);
+                }
+            }
+            row_offsets.push(weights.len());
+        }
+
+        Ok(SparseMatrix {
+            columns: self.site_count(),
+            row_offsets,
+            column_indices,
+            weights,
+        })
+    }
+}
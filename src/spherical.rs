@@ -0,0 +1,531 @@
+use std::collections::{HashMap, HashSet};
+
+use delaunator::EMPTY;
+use rstar::{primitives::GeomWithData, RTree};
+
+use crate::{
+    util::{next_harfedge, orient3d, prev_harfedge},
+    InterpolatorError, Lerpable, Point, Point3D,
+};
+
+/// Provides method for calculating natural neighbor interpolation on the unit sphere.
+///
+/// The points are given in degrees, as `Point { x: longitude, y: latitude }`,
+/// so the interpolation has no seams at the antimeridian and no distortion at the poles.
+///
+/// This includes:
+///  - Cloned point data on the unit sphere
+///  - RTree structure to find the nearest site as the origin of the walk to the triangle containing the point
+///  - Spherical Delaunay triangulation (the convex hull of the points on the unit sphere) to construct the boyer-watson envelope
+///
+/// The sites are weighted by Sibson coordinates: the area of the spherical voronoi cell stolen from each site.
+///
+/// If all the sites are in a hemisphere, the points outside the (spherical) convex hull of the sites are not interpolated.
+/// The sites at exactly the same position as a preceding site are ignored.
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Point, SphericalInterpolator};
+///
+/// // the vertices of an octahedron
+/// let points = [
+///     Point { x: 0.0, y: 0.0 },
+///     Point { x: 90.0, y: 0.0 },
+///     Point { x: 180.0, y: 0.0 },
+///     Point { x: -90.0, y: 0.0 },
+///     Point { x: 0.0, y: 90.0 },
+///     Point { x: 0.0, y: -90.0 },
+/// ];
+/// let values = [0.0f64, 1.0, 2.0, 3.0, 4.0, 5.0];
+///
+/// let interpolator = SphericalInterpolator::new(&points);
+///
+/// // the center of the face (0.0, 0.0) - (90.0, 0.0) - north pole
+/// let ptarget = Point { x: 45.0, y: (1.0f64 / 3.0f64.sqrt()).asin().to_degrees() };
+/// let value = interpolator
+///     .interpolate(&values, ptarget.clone())
+///     .unwrap()
+///     .unwrap();
+/// assert!((value - 5.0 / 3.0).abs() < 1e-6);
+///
+/// // across the antimeridian
+/// let value = interpolator
+///     .interpolate(&values, Point { x: -180.0, y: 0.0 })
+///     .unwrap()
+///     .unwrap();
+/// assert!((value - 2.0).abs() < 1e-6);
+/// ```
+#[derive(Clone)]
+pub struct SphericalInterpolator {
+    // the sites on the unit sphere
+    points: Vec<Point3D>,
+    // the triangles are counterclockwise when viewed from outside the sphere
+    triangles: Vec<usize>,
+    // EMPTY on the boundary of the triangulation (only if the sites are in a hemisphere)
+    harfedges: Vec<usize>,
+    // the circumcenters of the triangles on the unit sphere
+    circumcenters: Vec<Point3D>,
+    // a harfedge starting from each site (EMPTY for the ignored sites)
+    vertex_harfedges: Vec<usize>,
+    tree: RTree<GeomWithData<[f64; 3], usize>>,
+}
+
+/// Convert the longitude and the latitude (in degrees) to the point on the unit sphere.
+fn to_unit_vector(p: &Point) -> Point3D {
+    let (lon, lat) = (p.x.to_radians(), p.y.to_radians());
+    Point3D {
+        x: lat.cos() * lon.cos(),
+        y: lat.cos() * lon.sin(),
+        z: lat.sin(),
+    }
+}
+
+static ORIGIN: Point3D = Point3D {
+    x: 0.,
+    y: 0.,
+    z: 0.,
+};
+
+fn sub(a: &Point3D, b: &Point3D) -> [f64; 3] {
+    [a.x - b.x, a.y - b.y, a.z - b.z]
+}
+
+fn dot(a: &Point3D, b: &Point3D) -> f64 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> Point3D {
+    Point3D {
+        x: a[1] * b[2] - a[2] * b[1],
+        y: a[2] * b[0] - a[0] * b[2],
+        z: a[0] * b[1] - a[1] * b[0],
+    }
+}
+
+/// The circumcenter of the counterclockwise triangle on the unit sphere.
+fn spherical_circumcenter(a: &Point3D, b: &Point3D, c: &Point3D) -> Point3D {
+    let n = cross(sub(b, a), sub(c, a));
+    let l = dot(&n, &n).sqrt();
+    Point3D {
+        x: n.x / l,
+        y: n.y / l,
+        z: n.z / l,
+    }
+}
+
+/// The area of the spherical polygon on the unit sphere.
+fn spherical_polygon_area(polygon: &[Point3D]) -> f64 {
+    let a = &polygon[0];
+    let mut area = 0.;
+    for i in 1..polygon.len().saturating_sub(1) {
+        let (b, c) = (&polygon[i], &polygon[i + 1]);
+        let triple = dot(a, &cross([b.x, b.y, b.z], [c.x, c.y, c.z]));
+        area += 2. * triple.atan2(1. + dot(a, b) + dot(b, c) + dot(c, a));
+    }
+    area.abs()
+}
+
+/// The convex hull of the points on the unit sphere, built incrementally.
+struct ConvexHull<'a> {
+    points: &'a [Point3D],
+    // the faces are counterclockwise when viewed from outside
+    faces: Vec<[usize; 3]>,
+    // adjacent[f][k] is the face sharing the edge from faces[f][k] to faces[f][(k + 1) % 3]
+    adjacent: Vec<[usize; 3]>,
+    alive: Vec<bool>,
+    // the points not inserted yet, which are outside of each face
+    conflicts: Vec<Vec<usize>>,
+    // a face visible from each point not inserted yet
+    conflict_faces: Vec<usize>,
+}
+
+impl<'a> ConvexHull<'a> {
+    fn visible(&self, f: usize, p: usize) -> bool {
+        let [a, b, c] = self.faces[f].map(|i| &self.points[i]);
+        orient3d(a, b, c, &self.points[p]) < 0.
+    }
+
+    /// Add the face oriented counterclockwise when viewed from the side opposite to `inside`.
+    fn add_face(&mut self, mut face: [usize; 3], inside: &Point3D) {
+        let [a, b, c] = face.map(|i| &self.points[i]);
+        if orient3d(a, b, c, inside) < 0. {
+            face.swap(1, 2);
+        }
+        self.faces.push(face);
+        self.adjacent.push([EMPTY; 3]);
+        self.alive.push(true);
+        self.conflicts.push(Vec::new());
+    }
+
+    /// Find a face visible from the point, checking the candidates first.
+    fn find_visible(&self, p: usize, candidates: &[usize]) -> Option<usize> {
+        candidates
+            .iter()
+            .copied()
+            .find(|f| self.visible(*f, p))
+            .or_else(|| (0..self.faces.len()).find(|f| self.alive[*f] && self.visible(*f, p)))
+    }
+
+    /// Build the convex hull of the sites.
+    /// Returns None if all the sites are on a plane.
+    fn build(points: &'a [Point3D], sites: &[usize]) -> Option<Self> {
+        let a = *sites.first()?;
+        let b = *sites.get(1)?;
+        let c = *sites.get(2)?;
+        let d = *sites
+            .iter()
+            .find(|d| orient3d(&points[a], &points[b], &points[c], &points[**d]) != 0.)?;
+
+        let mut hull = Self {
+            points,
+            faces: Vec::new(),
+            adjacent: Vec::new(),
+            alive: Vec::new(),
+            conflicts: Vec::new(),
+            conflict_faces: vec![EMPTY; points.len()],
+        };
+        for (face, inside) in [
+            ([a, b, c], d),
+            ([a, b, d], c),
+            ([a, c, d], b),
+            ([b, c, d], a),
+        ] {
+            hull.add_face(face, &points[inside]);
+        }
+        hull.link_faces(&[0, 1, 2, 3]);
+
+        for p in sites.iter().filter(|p| ![a, b, c, d].contains(p)) {
+            if let Some(f) = hull.find_visible(*p, &[0, 1, 2, 3]) {
+                hull.conflicts[f].push(*p);
+                hull.conflict_faces[*p] = f;
+            }
+        }
+
+        for p in sites.iter().filter(|p| ![a, b, c, d].contains(p)) {
+            hull.insert(*p);
+        }
+        Some(hull)
+    }
+
+    /// Set the adjacency between the faces by their shared edges.
+    fn link_faces(&mut self, faces: &[usize]) {
+        let mut edges = HashMap::new();
+        for f in faces {
+            for k in 0..3 {
+                let (u, v) = (self.faces[*f][k], self.faces[*f][(k + 1) % 3]);
+                if let Some((g, l)) = edges.remove(&(v, u)) {
+                    self.adjacent[*f][k] = g;
+                    self.adjacent[g][l] = *f;
+                } else {
+                    edges.insert((u, v), (*f, k));
+                }
+            }
+        }
+    }
+
+    /// Insert the point into the hull, replacing the faces visible from it.
+    fn insert(&mut self, p: usize) {
+        let start = self.conflict_faces[p];
+        if start == EMPTY {
+            // not outside the hull
+            return;
+        }
+
+        // the faces visible from the point
+        let mut visible = vec![start];
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(f) = stack.pop() {
+            for g in self.adjacent[f] {
+                if visited.insert(g) && self.visible(g, p) {
+                    visible.push(g);
+                    stack.push(g);
+                }
+            }
+        }
+        let inside = visible.iter().copied().collect::<HashSet<_>>();
+
+        // the new faces on the horizon edges
+        let mut new_faces = Vec::new();
+        let mut starting = HashMap::new();
+        for f in &visible {
+            for k in 0..3 {
+                let g = self.adjacent[*f][k];
+                if inside.contains(&g) {
+                    continue;
+                }
+                let (u, v) = (self.faces[*f][k], self.faces[*f][(k + 1) % 3]);
+                let l = (0..3).find(|l| self.faces[g][*l] == v).unwrap();
+                self.faces.push([u, v, p]);
+                self.adjacent.push([g, EMPTY, EMPTY]);
+                self.alive.push(true);
+                self.conflicts.push(Vec::new());
+                let n = self.faces.len() - 1;
+                self.adjacent[g][l] = n;
+                starting.insert(u, n);
+                new_faces.push(n);
+            }
+        }
+        for n in &new_faces {
+            let [_, v, _] = self.faces[*n];
+            let next = starting[&v];
+            self.adjacent[*n][1] = next;
+            self.adjacent[next][2] = *n;
+        }
+
+        for f in &visible {
+            self.alive[*f] = false;
+            for q in std::mem::take(&mut self.conflicts[*f]) {
+                if q == p {
+                    continue;
+                }
+                self.conflict_faces[q] = EMPTY;
+                if let Some(g) = self.find_visible(q, &new_faces) {
+                    self.conflicts[g].push(q);
+                    self.conflict_faces[q] = g;
+                }
+            }
+        }
+    }
+}
+
+impl SphericalInterpolator {
+    /// Create a new SphericalInterpolator from a slice of points given as `Point { x: longitude, y: latitude }` in degrees.
+    pub fn new<P>(points: &[P]) -> Self
+    where
+        P: Into<Point> + Clone,
+    {
+        let points = points
+            .iter()
+            .map(|p| to_unit_vector(&(*p).clone().into()))
+            .collect::<Vec<Point3D>>();
+
+        let mut first = HashMap::new();
+        let sites = (0..points.len())
+            .filter(|i| {
+                let p = &points[*i];
+                *first
+                    .entry((p.x.to_bits(), p.y.to_bits(), p.z.to_bits()))
+                    .or_insert(*i)
+                    == *i
+            })
+            .collect::<Vec<_>>();
+
+        let mut interpolator = Self {
+            triangles: Vec::new(),
+            harfedges: Vec::new(),
+            circumcenters: Vec::new(),
+            vertex_harfedges: vec![EMPTY; points.len()],
+            tree: RTree::bulk_load(
+                sites
+                    .iter()
+                    .map(|i| GeomWithData::new([points[*i].x, points[*i].y, points[*i].z], *i))
+                    .collect(),
+            ),
+            points,
+        };
+
+        if let Some(hull) = ConvexHull::build(&interpolator.points, &sites) {
+            // The faces facing the origin are not spherical Delaunay triangles (only if the sites are in a hemisphere).
+            let kept = (0..hull.faces.len())
+                .filter(|f| {
+                    let [a, b, c] = hull.faces[*f].map(|i| &interpolator.points[i]);
+                    hull.alive[*f] && orient3d(a, b, c, &ORIGIN) > 0.
+                })
+                .collect::<Vec<_>>();
+            let index = kept
+                .iter()
+                .enumerate()
+                .map(|(t, f)| (*f, t))
+                .collect::<HashMap<_, _>>();
+
+            for (t, f) in kept.iter().enumerate() {
+                let face = hull.faces[*f];
+                interpolator.triangles.extend(face);
+                for k in 0..3 {
+                    let harfedge = index.get(&hull.adjacent[*f][k]).map_or(EMPTY, |g| {
+                        let l = (0..3)
+                            .find(|l| hull.faces[kept[*g]][*l] == face[(k + 1) % 3])
+                            .unwrap();
+                        g * 3 + l
+                    });
+                    interpolator.harfedges.push(harfedge);
+                    interpolator.vertex_harfedges[face[k]] = t * 3 + k;
+                }
+                let [a, b, c] = face.map(|i| &interpolator.points[i]);
+                interpolator
+                    .circumcenters
+                    .push(spherical_circumcenter(a, b, c));
+            }
+        }
+        interpolator
+    }
+
+    /// Check if the point is on the left side of the great circle from `a` to `b` (or on it).
+    fn left_of(&self, a: usize, b: usize, ptarget: &Point3D) -> bool {
+        orient3d(&self.points[a], &self.points[b], ptarget, &ORIGIN) >= 0.
+    }
+
+    /// Find the triangle containing the point by walking from the triangle `it`.
+    fn walk_to_triangle(&self, ptarget: &Point3D, mut it: usize) -> Option<usize> {
+        'walk: for _ in 0..self.triangles.len() / 3 + 1 {
+            for e in it * 3..it * 3 + 3 {
+                if !self.left_of(self.triangles[e], self.triangles[next_harfedge(e)], ptarget) {
+                    let opposite = self.harfedges[e];
+                    if opposite == EMPTY {
+                        // left the triangulation
+                        return None;
+                    }
+                    it = opposite / 3;
+                    continue 'walk;
+                }
+            }
+            return Some(it);
+        }
+        None
+    }
+
+    /// Perform natural neighbor interpolation.
+    ///
+    /// The 'apply_weight' function is called if the point is iterated as one of the natural neighbors.
+    /// The first argument is the index of the point, the second argument is the weight of the point,
+    /// and the third argument is the tentative sum of the weight.
+    fn perform_interpoation(
+        &self,
+        ptarget: &Point3D,
+        apply_weight: &mut impl FnMut(usize, f64, f64),
+    ) -> Result<(), InterpolatorError> {
+        if self.triangles.is_empty() {
+            return Ok(());
+        }
+        let nearest = if let Some(nearest) = self
+            .tree
+            .nearest_neighbor(&[ptarget.x, ptarget.y, ptarget.z])
+        {
+            nearest.data
+        } else {
+            return Ok(());
+        };
+        if self.points[nearest] == *ptarget {
+            apply_weight(nearest, 1., 1.);
+            return Ok(());
+        }
+
+        let start = match self.vertex_harfedges[nearest] {
+            EMPTY => 0,
+            e => e / 3,
+        };
+        let it = if let Some(it) = self.walk_to_triangle(ptarget, start) {
+            it
+        } else {
+            return Ok(());
+        };
+
+        // the triangles whose circumcircle contains the point (the boyer-watson cavity)
+        let mut cavity = HashSet::from([it]);
+        let mut stack = vec![it];
+        while let Some(it) = stack.pop() {
+            for e in it * 3..it * 3 + 3 {
+                let opposite = self.harfedges[e];
+                if opposite == EMPTY || cavity.contains(&(opposite / 3)) {
+                    continue;
+                }
+                let ot = opposite / 3;
+                let [a, b, c] = [0, 1, 2].map(|k| &self.points[self.triangles[ot * 3 + k]]);
+                if orient3d(a, b, c, ptarget) < 0. {
+                    cavity.insert(ot);
+                    stack.push(ot);
+                }
+            }
+        }
+
+        // the edges on the boundary of the cavity, keyed by the end site
+        let mut incoming = HashMap::new();
+        let mut boundary = Vec::new();
+        for it in &cavity {
+            for e in it * 3..it * 3 + 3 {
+                let opposite = self.harfedges[e];
+                if opposite == EMPTY || !cavity.contains(&(opposite / 3)) {
+                    incoming.insert(self.triangles[next_harfedge(e)], e);
+                    boundary.push(e);
+                }
+            }
+        }
+        boundary.sort_unstable();
+
+        let mut tmp_weight_sum = 0.;
+        for eout in boundary {
+            let (i, j) = (self.triangles[eout], self.triangles[next_harfedge(eout)]);
+            let ein = incoming[&i];
+            let h = self.triangles[ein];
+            let (pi, pj, ph) = (&self.points[i], &self.points[j], &self.points[h]);
+
+            // the area stolen from the site is bounded by the new voronoi edge between the point and the site,
+            // and the voronoi vertices of the site inside the cavity
+            let mut polygon = vec![spherical_circumcenter(pi, pj, ptarget)];
+            let mut e = eout;
+            loop {
+                polygon.push(self.circumcenters[e / 3].clone());
+                let prev = prev_harfedge(e);
+                if prev == ein || self.harfedges[prev] == EMPTY {
+                    break;
+                }
+                e = self.harfedges[prev];
+            }
+            polygon.push(spherical_circumcenter(ph, pi, ptarget));
+
+            let weight = spherical_polygon_area(&polygon);
+            tmp_weight_sum += weight;
+            apply_weight(i, weight, tmp_weight_sum);
+        }
+        Ok(())
+    }
+
+    /// Interpolate the value at the point given as `Point { x: longitude, y: latitude }` in degrees.
+    /// If the point is outside the triangulation, None is returned.
+    pub fn interpolate<P, V>(
+        &self,
+        values: &[V],
+        ptarget: P,
+    ) -> Result<Option<V>, InterpolatorError>
+    where
+        P: Into<Point>,
+        V: Lerpable,
+    {
+        if self.points.len() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let mut terms = Vec::new();
+        self.perform_interpoation(&to_unit_vector(&ptarget.into()), &mut |i, weight, _| {
+            terms.push((&values[i], weight));
+        })?;
+
+        Ok((!terms.is_empty()).then(|| V::blend(&terms)))
+    }
+
+    /// Query the result of the interpolation as a list of indices of sites to be weighted.
+    /// If the point is outside the triangulation, None is returned.
+    pub fn query_weights<P>(
+        &self,
+        ptarget: P,
+    ) -> Result<Option<Vec<(usize, f64)>>, InterpolatorError>
+    where
+        P: Into<Point>,
+    {
+        let mut weights = Vec::new();
+        let mut weight_sum = 0.;
+        self.perform_interpoation(&to_unit_vector(&ptarget.into()), &mut |i, weight, _| {
+            weight_sum += weight;
+            weights.push((i, weight));
+        })?;
+
+        if weight_sum == 0. {
+            Ok(None)
+        } else {
+            Ok(Some(
+                weights.iter().map(|(i, w)| (*i, w / weight_sum)).collect(),
+            ))
+        }
+    }
+}
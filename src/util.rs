@@ -1,4 +1,4 @@
-use crate::Point;
+use crate::{Point, Point3D};
 
 pub(crate) fn circumcenter(triangle: &[&Point; 3]) -> Point {
     let p1 = triangle[0];
@@ -17,12 +17,85 @@ pub(crate) fn circumcenter(triangle: &[&Point; 3]) -> Point {
     Point { x: ux, y: uy }
 }
 
-pub(crate) fn circumcircle_with_radius_2(triangle: &[&Point; 3]) -> (Point, f64) {
-    let p1 = triangle[0];
-    let circumcenter = circumcenter(triangle);
-    let circumradius2 = (p1.x - circumcenter.x).powi(2) + (p1.y - circumcenter.y).powi(2);
+/// The orientation of the points, calculated with the adaptive precision arithmetic.
+/// Returns a positive value if the points are counterclockwise, a negative value if clockwise, and zero if collinear.
+pub(crate) fn orient(a: &Point, b: &Point, c: &Point) -> f64 {
+    robust::orient2d(coord(a), coord(b), coord(c))
+}
+
+/// Check if the point is strictly inside the circumcircle of the triangle,
+/// calculated with the adaptive precision arithmetic.
+pub(crate) fn in_circumcircle(triangle: &[&Point; 3], p: &Point) -> bool {
+    let [a, b, c] = triangle.map(coord);
+    let incircle = robust::incircle(a, b, c, coord(p));
+    // the sign of incircle is reversed for the clockwise triangles
+    match orient(triangle[0], triangle[1], triangle[2]) {
+        o if o > 0. => incircle > 0.,
+        o if o < 0. => incircle < 0.,
+        _ => false,
+    }
+}
+
+fn coord(p: &Point) -> robust::Coord<f64> {
+    robust::Coord { x: p.x, y: p.y }
+}
 
-    (circumcenter, circumradius2)
+/// The orientation of the points in 3D, calculated with the adaptive precision arithmetic.
+/// Returns a positive value if `d` is below the plane of `a`, `b` and `c` (which are counterclockwise when viewed from above),
+/// a negative value if above, and zero if coplanar.
+pub(crate) fn orient3d(a: &Point3D, b: &Point3D, c: &Point3D, d: &Point3D) -> f64 {
+    robust::orient3d(coord3d(a), coord3d(b), coord3d(c), coord3d(d))
+}
+
+/// Check if the point is strictly inside the circumsphere of the tetrahedron,
+/// calculated with the adaptive precision arithmetic.
+pub(crate) fn in_circumsphere(tetrahedron: &[&Point3D; 4], p: &Point3D) -> bool {
+    let [a, b, c, d] = tetrahedron.map(coord3d);
+    let insphere = robust::insphere(a, b, c, d, coord3d(p));
+    // the sign of insphere is reversed for the negatively oriented tetrahedra
+    match orient3d(
+        tetrahedron[0],
+        tetrahedron[1],
+        tetrahedron[2],
+        tetrahedron[3],
+    ) {
+        o if o > 0. => insphere > 0.,
+        o if o < 0. => insphere < 0.,
+        _ => false,
+    }
+}
+
+fn coord3d(p: &Point3D) -> robust::Coord3D<f64> {
+    robust::Coord3D {
+        x: p.x,
+        y: p.y,
+        z: p.z,
+    }
+}
+
+/// The circumcenter of the tetrahedron.
+pub(crate) fn circumcenter3d(tetrahedron: &[&Point3D; 4]) -> Point3D {
+    let o = tetrahedron[0];
+    let [a, b, c] = [1, 2, 3].map(|i| {
+        let p = tetrahedron[i];
+        [p.x - o.x, p.y - o.y, p.z - o.z]
+    });
+    let len2 = |v: [f64; 3]| v[0] * v[0] + v[1] * v[1] + v[2] * v[2];
+    let cross = |u: [f64; 3], v: [f64; 3]| {
+        [
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ]
+    };
+    let (bc, ca, ab) = (cross(b, c), cross(c, a), cross(a, b));
+    let d = 2. * (a[0] * bc[0] + a[1] * bc[1] + a[2] * bc[2]);
+    let (la, lb, lc) = (len2(a), len2(b), len2(c));
+    Point3D {
+        x: o.x + (la * bc[0] + lb * ca[0] + lc * ab[0]) / d,
+        y: o.y + (la * bc[1] + lb * ca[1] + lc * ab[1]) / d,
+        z: o.z + (la * bc[2] + lb * ca[2] + lc * ab[2]) / d,
+    }
 }
 
 pub(crate) fn next_harfedge(e: usize) -> usize {
@@ -32,3 +105,26 @@ pub(crate) fn next_harfedge(e: usize) -> usize {
         e + 1
     }
 }
+
+pub(crate) fn prev_harfedge(e: usize) -> usize {
+    if e % 3 == 0 {
+        e + 2
+    } else {
+        e - 1
+    }
+}
+
+pub(crate) fn distance_2(p1: &Point, p2: &Point) -> f64 {
+    (p1.x - p2.x).powi(2) + (p1.y - p2.y).powi(2)
+}
+
+/// The derivative of the circumcenter `c` of the triangle (p, a, b) with respect to `p` is `v (c - p)^T`.
+/// Returns `v`, or zero if the points are collinear and the circumcenter is at infinity.
+pub(crate) fn circumcenter_derivative(p: &Point, a: &Point, b: &Point) -> [f64; 2] {
+    let (m11, m12, m21, m22) = (a.x - p.x, a.y - p.y, b.x - p.x, b.y - p.y);
+    let det = m11 * m22 - m12 * m21;
+    if det == 0. {
+        return [0., 0.];
+    }
+    [(m22 - m12) / det, (m11 - m21) / det]
+}
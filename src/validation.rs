@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+
+use crate::{
+    util::{in_circumcircle, next_harfedge, orient, prev_harfedge},
+    Interpolator, InterpolatorError, MergePolicy, Point,
+};
+
+impl Interpolator {
+    /// Create a new Interpolator from a precomputed triangulation of the points, such as `delaunator::Triangulation`.
+    ///
+    /// The triangles and the halfedges are given in the same form as `delaunator`:
+    /// the triangles are clockwise, and the halfedges on the convex hull are `delaunator::EMPTY`.
+    /// Only the RTree is built from them, without triangulating the points again.
+    ///
+    /// Returns
+    ///  - `InterpolatorError::NonFiniteCoordinate` if a point has a NaN or infinite coordinate
+    ///  - `InterpolatorError::TooFewPoints` if there are fewer than three distinct points
+    ///  - `InterpolatorError::InvalidSiteIndex` if a triangle refers to a point which does not exist
+    ///  - `InterpolatorError::InvalidHarfedge` if a halfedge is not paired with the opposite halfedge of the adjacent triangle
+    ///  - `InterpolatorError::InvalidTriangle` if a triangle is degenerate or counterclockwise
+    ///  - `InterpolatorError::UnusedSite` if a point is not a vertex of any triangle
+    ///  - `InterpolatorError::NotDelaunay` or `InterpolatorError::NonConvexHull` if the triangulation is not Delaunay
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// let triangulation = delaunator::triangulate(&points);
+    /// let interpolator = Interpolator::from_triangulation(
+    ///     &points,
+    ///     &triangulation.triangles,
+    ///     &triangulation.halfedges,
+    /// )
+    /// .unwrap();
+    ///
+    /// let value = interpolator
+    ///     .interpolate(&values, Point { x: 0.5, y: 0.5 })
+    ///     .unwrap()
+    ///     .unwrap();
+    /// assert!((value - 1.0).abs() < 1e-6);
+    /// ```
+    pub fn from_triangulation<P>(
+        points: &[P],
+        triangles: &[usize],
+        halfedges: &[usize],
+    ) -> Result<Self, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+    {
+        let (points, merged) = Self::validate_points(points, 3)?;
+        let mut interpolator = Self::untriangulated(points, merged, 0., MergePolicy::default());
+        interpolator.triangles = triangles.to_vec();
+        interpolator.harfedges = halfedges.to_vec();
+
+        interpolator.validate_triangulation()?;
+        // the triangulation may refer to any of the duplicated points
+        for i in interpolator.triangles.iter_mut() {
+            *i = interpolator.merged[*i];
+        }
+        interpolator.validate_coverage()?;
+        interpolator.validate_delaunay()?;
+        interpolator.rebuild_tree();
+        Ok(interpolator)
+    }
+
+    /// Check if the triangulation is consistent:
+    /// the triangles refer to the existing sites, and each harfedge is paired with the opposite harfedge of the adjacent triangle.
+    pub(crate) fn validate_triangulation(&self) -> Result<(), InterpolatorError> {
+        if self.triangles.len() % 3 != 0 || self.harfedges.len() != self.triangles.len() {
+            return Err(InterpolatorError::InvalidHarfedge(self.harfedges.len()));
+        }
+        if let Some(i) = self.triangles.iter().find(|i| **i >= self.points.len()) {
+            return Err(InterpolatorError::InvalidSiteIndex(*i));
+        }
+
+        for (e, opposite) in self.harfedges.iter().enumerate() {
+            if *opposite >= self.harfedges.len() {
+                // on the convex hull
+                continue;
+            }
+            if self.harfedges[*opposite] != e
+                || self.triangles[e] != self.triangles[next_harfedge(*opposite)]
+                || self.triangles[next_harfedge(e)] != self.triangles[*opposite]
+            {
+                return Err(InterpolatorError::InvalidHarfedge(e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if the triangles of the (consistent) triangulation are clockwise and not degenerate, as the triangles given by `delaunator`.
+    pub(crate) fn validate_orientation(&self) -> Result<(), InterpolatorError> {
+        for it in 0..self.triangles.len() / 3 {
+            let [a, b, c] = [0, 1, 2].map(|k| &self.points[self.triangles[it * 3 + k]]);
+            if orient(a, b, c) >= 0. {
+                return Err(InterpolatorError::InvalidTriangle(it));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if every site is a vertex of the triangulation.
+    fn validate_coverage(&self) -> Result<(), InterpolatorError> {
+        let mut used = vec![false; self.points.len()];
+        for i in &self.triangles {
+            used[*i] = true;
+        }
+        match (0..self.points.len()).find(|i| self.merged[*i] == *i && !used[*i]) {
+            Some(i) => Err(InterpolatorError::UnusedSite(i)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check if the (consistent) triangulation is Delaunay:
+    /// the triangles are clockwise, no site is inside the circumcircle of the adjacent triangle, and the convex hull is convex.
+    fn validate_delaunay(&self) -> Result<(), InterpolatorError> {
+        let triangle_points =
+            |it: usize| [0, 1, 2].map(|k| &self.points[self.triangles[it * 3 + k]]);
+
+        self.validate_orientation()?;
+
+        // the locally Delaunay edges make the Delaunay triangulation
+        let mut hull = HashMap::new();
+        for (e, opposite) in self.harfedges.iter().enumerate() {
+            if *opposite >= self.harfedges.len() {
+                if hull.insert(self.triangles[e], e).is_some() {
+                    // the boundary touches itself at the site
+                    return Err(InterpolatorError::NonConvexHull(self.triangles[e]));
+                }
+                continue;
+            }
+            let site = self.triangles[prev_harfedge(*opposite)];
+            if in_circumcircle(&triangle_points(e / 3), &self.points[site]) {
+                return Err(InterpolatorError::NotDelaunay(e / 3, site));
+            }
+        }
+
+        // the hull edges are clockwise as well as the triangles
+        for e in hull.values() {
+            let (a, b) = (self.triangles[*e], self.triangles[next_harfedge(*e)]);
+            let next = hull.get(&b).ok_or(InterpolatorError::NonConvexHull(b))?;
+            let c = self.triangles[next_harfedge(*next)];
+            if orient(&self.points[a], &self.points[b], &self.points[c]) > 0. {
+                return Err(InterpolatorError::NonConvexHull(b));
+            }
+        }
+
+        // the hull edges make a single cycle
+        if let Some(start) = hull.values().next() {
+            let next = |e: usize| hull[&self.triangles[next_harfedge(e)]];
+            let mut e = *start;
+            for _ in 1..hull.len() {
+                e = next(e);
+                if e == *start {
+                    return Err(InterpolatorError::NonConvexHull(self.triangles[e]));
+                }
+            }
+            if next(e) != *start {
+                return Err(InterpolatorError::NonConvexHull(self.triangles[e]));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::Point;
+
+/// The label of the edges which are not made by any bisector of the sites.
+pub(crate) static NO_LABEL: usize = usize::MAX;
+
+/// Convex polygon to be clipped by the bisectors between sites.
+///
+/// This is used to calculate (a part of) the voronoi cell of a point directly,
+/// where the cell cannot be obtained from the boyer-watson envelope.
+/// Each edge holds the label of the bisector which made it.
+#[derive(Debug, Clone)]
+pub(crate) struct ConvexPolygon {
+    vertices: Vec<Point>,
+    // labels[i] is the label of the edge from vertices[i] to vertices[i + 1]
+    labels: Vec<usize>,
+}
+
+impl ConvexPolygon {
+    /// Create an axis-aligned square centered on `center`.
+    pub fn square(center: &Point, half_size: f64) -> Self {
+        Self {
+            vertices: vec![
+                Point {
+                    x: center.x - half_size,
+                    y: center.y - half_size,
+                },
+                Point {
+                    x: center.x + half_size,
+                    y: center.y - half_size,
+                },
+                Point {
+                    x: center.x + half_size,
+                    y: center.y + half_size,
+                },
+                Point {
+                    x: center.x - half_size,
+                    y: center.y + half_size,
+                },
+            ],
+            labels: vec![NO_LABEL; 4],
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.len() < 3
+    }
+
+    /// Keep the part of the polygon which is closer to `site` than to `other`.
+    /// The new edge on the bisector is labeled with `label`.
+    pub fn clip_bisector(&mut self, site: &Point, other: &Point, label: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mx = (site.x + other.x) / 2.;
+        let my = (site.y + other.y) / 2.;
+        let nx = other.x - site.x;
+        let ny = other.y - site.y;
+
+        // signed distance (scaled) from the bisector. negative values are on the side of `site`.
+        let side = |p: &Point| (p.x - mx) * nx + (p.y - my) * ny;
+
+        let mut vertices = Vec::with_capacity(self.vertices.len() + 1);
+        let mut labels = Vec::with_capacity(self.vertices.len() + 1);
+        for (i, current) in self.vertices.iter().enumerate() {
+            let next = &self.vertices[(i + 1) % self.vertices.len()];
+            let (sc, sn) = (side(current), side(next));
+            let intersection = || {
+                let t = sc / (sc - sn);
+                Point {
+                    x: current.x + (next.x - current.x) * t,
+                    y: current.y + (next.y - current.y) * t,
+                }
+            };
+            if sc < 0. && sn > 0. {
+                // leaving the region
+                vertices.push(current.clone());
+                labels.push(self.labels[i]);
+                vertices.push(intersection());
+                labels.push(label);
+            } else if sc <= 0. {
+                vertices.push(current.clone());
+                labels.push(if sn > 0. { label } else { self.labels[i] });
+            } else if sn < 0. {
+                // entering the region
+                vertices.push(intersection());
+                labels.push(self.labels[i]);
+            }
+        }
+        self.vertices = vertices;
+        self.labels = labels;
+    }
+
+    pub fn area(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let mut area2 = 0.;
+        for (i, current) in self.vertices.iter().enumerate() {
+            let next = &self.vertices[(i + 1) % self.vertices.len()];
+            area2 += current.x * next.y - next.x * current.y;
+        }
+        area2.abs() / 2.
+    }
+
+    /// The endpoints of the edge with the label (counterclockwise),
+    /// and the labels of the previous and the next edges.
+    pub fn edge(&self, label: usize) -> Option<(Point, Point, usize, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+        let n = self.vertices.len();
+        let i = self.labels.iter().position(|l| *l == label)?;
+        Some((
+            self.vertices[i].clone(),
+            self.vertices[(i + 1) % n].clone(),
+            self.labels[(i + n - 1) % n],
+            self.labels[(i + 1) % n],
+        ))
+    }
+}
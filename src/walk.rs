@@ -0,0 +1,104 @@
+use crate::{
+    util::{next_harfedge, orient},
+    Interpolator, InterpolatorError, Lerpable, Point,
+};
+
+/// Holds the triangle found in the previous query,
+/// which is used as the starting point to find the triangle containing the next point.
+///
+/// Used by `Interpolator::interpolate_from_hint`.
+/// A hint can be reused for any interpolator, but it is effective only for the queries close to each other.
+#[derive(Debug, Clone, Default)]
+pub struct QueryHint {
+    pub(crate) triangle: Option<usize>,
+}
+
+impl QueryHint {
+    /// Create an empty hint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Interpolator {
+    /// Find the triangle containing the point by walking over the triangulation from the triangle `it`.
+    ///
+    /// In each step, the walk moves across an edge of the current triangle which the point is beyond (visibility walk).
+    /// None is returned when the walk leaves the convex hull, then the RTree should be used instead.
+    pub(crate) fn walk_to_triangle(&self, ptarget: &Point, it: usize) -> Option<usize> {
+        if it * 3 >= self.triangles.len() {
+            return None;
+        }
+
+        let mut it = it;
+        'walk: for step in 0..self.triangles.len() / 3 {
+            // rotate the first edge to be checked to avoid cycles
+            for k in 0..3 {
+                let e = it * 3 + (k + step) % 3;
+                let a = &self.points[self.triangles[e]];
+                let b = &self.points[self.triangles[next_harfedge(e)]];
+                // the triangles are clockwise, so the outside is on the left side of the edges
+                if orient(a, b, ptarget) > 0. {
+                    let opposite = self.harfedges[e];
+                    if opposite >= self.harfedges.len() {
+                        // left the convex hull
+                        return None;
+                    }
+                    it = opposite / 3;
+                    continue 'walk;
+                }
+            }
+            return Some(it);
+        }
+        None
+    }
+
+    /// Interpolate the value at the point, starting the search of the triangle from the hint.
+    ///
+    /// The triangle containing the point is found by walking over the triangulation from the triangle of the previous query,
+    /// so it is faster than `interpolate` for the queries close to each other.
+    /// The RTree is used only when the walk cannot find the triangle (e.g. the first query, or the point outside the convex hull).
+    /// The hint is updated for the next query.
+    ///
+    /// # Example
+    /// ```
+    /// use naturalneighbor::{Interpolator, Point, QueryHint};
+    ///
+    /// let points = [
+    ///     Point { x: 0.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 0.0 },
+    ///     Point { x: 1.0, y: 1.0 },
+    ///     Point { x: 0.0, y: 1.0 },
+    /// ];
+    /// let values = [0.0f64, 1.0, 2.0, 1.0];
+    ///
+    /// let interpolator = Interpolator::new(&points);
+    /// let mut hint = QueryHint::new();
+    ///
+    /// for i in 1..10 {
+    ///     let t = i as f64 / 10.0;
+    ///     let value = interpolator
+    ///         .interpolate_from_hint(&values, Point { x: t, y: 0.5 }, &mut hint)
+    ///         .unwrap()
+    ///         .unwrap();
+    ///     assert!((value - (t + 0.5)).abs() < 1e-6);
+    /// }
+    /// ```
+    pub fn interpolate_from_hint<P, V>(
+        &self,
+        values: &[V],
+        ptarget: P,
+        hint: &mut QueryHint,
+    ) -> Result<Option<V>, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+        V: Lerpable,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        self.interpolate_with_hint(values, ptarget.into(), hint)
+            .map(|(value, _)| value)
+    }
+}
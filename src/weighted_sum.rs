@@ -0,0 +1,183 @@
+use crate::{Interpolator, InterpolatorError, Point, Rounded, Rounding};
+
+/// Defines objects that can be calculated as the weighted sum of the values of the sites.
+///
+/// Unlike `Lerpable::lerp`, all the values and their weights are given at once,
+/// so the rounding error is not accumulated by blending the values one by one,
+/// and the values which are naturally linear combinations (such as sparse vectors or distributions) can be interpolated.
+/// The implementations of [Lerpable](crate::Lerpable) for the floats and the integers blend the values with this trait as well.
+/// Used by `Interpolator::interpolate_weighted`.
+///
+/// This trait is implemented for `f64` and `f32` with the compensated (Kahan-Babuška) summation,
+/// the integers (rounded once after the summation), and the arrays and the tuples (up to 4 elements) of them.
+///
+/// # Example
+/// ```
+/// use naturalneighbor::{Interpolator, Point, WeightedSum};
+///
+/// // a distribution over the categories, whose length may vary
+/// #[derive(Clone, Debug)]
+/// pub struct Distribution(Vec<f64>);
+///
+/// impl WeightedSum for Distribution {
+///     fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+///         let len = terms.iter().map(|(v, _)| v.0.len()).max().unwrap_or(0);
+///         let mut sum = vec![0.0; len];
+///         for (v, w) in terms {
+///             for (s, p) in sum.iter_mut().zip(&v.0) {
+///                 *s += p * w;
+///             }
+///         }
+///         Distribution(sum)
+///     }
+/// }
+///
+/// let points = [
+///     Point { x: 0.0, y: 0.0 },
+///     Point { x: 1.0, y: 0.0 },
+///     Point { x: 0.0, y: 1.0 },
+/// ];
+/// let values = [
+///     Distribution(vec![1.0]),
+///     Distribution(vec![0.0, 1.0]),
+///     Distribution(vec![0.0, 0.0, 1.0]),
+/// ];
+///
+/// let interpolator = Interpolator::new(&points);
+/// let value = interpolator
+///     .interpolate_weighted(&values, Point { x: 0.5, y: 0.0 })
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(value.0, vec![0.5, 0.5]);
+/// ```
+pub trait WeightedSum: Sized {
+    /// Calculate the weighted sum of the values.
+    /// The terms are not empty, and the weights are normalized so that their sum is 1.
+    fn weighted_sum(terms: &[(&Self, f64)]) -> Self;
+}
+
+/// Normalize the weights of the terms so that their sum is 1.
+pub(crate) fn normalized<'a, V>(terms: &[(&'a V, f64)]) -> Vec<(&'a V, f64)> {
+    let weight_sum = compensated_sum(terms.iter().map(|(_, w)| *w));
+    terms.iter().map(|(v, w)| (*v, w / weight_sum)).collect()
+}
+
+/// The sum of the values by the compensated summation (Kahan-Babuška).
+fn compensated_sum(values: impl Iterator<Item = f64>) -> f64 {
+    let mut sum = 0.;
+    let mut compensation = 0.;
+    for v in values {
+        let t = sum + v;
+        compensation += if sum.abs() >= v.abs() {
+            (sum - t) + v
+        } else {
+            (v - t) + sum
+        };
+        sum = t;
+    }
+    sum + compensation
+}
+
+impl WeightedSum for f64 {
+    fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+        compensated_sum(terms.iter().map(|(v, w)| *v * w))
+    }
+}
+
+impl WeightedSum for f32 {
+    fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+        compensated_sum(terms.iter().map(|(v, w)| **v as f64 * w)) as f32
+    }
+}
+
+macro_rules! impl_weighted_sum_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl WeightedSum for $t {
+                fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+                    compensated_sum(terms.iter().map(|(v, w)| **v as f64 * w)).round() as $t
+                }
+            }
+
+            impl<R> WeightedSum for Rounded<$t, R>
+            where
+                R: Rounding,
+            {
+                fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+                    let sum = compensated_sum(terms.iter().map(|(v, w)| v.value as f64 * w));
+                    Self::new(R::round(sum) as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_weighted_sum_for_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl<V, const N: usize> WeightedSum for [V; N]
+where
+    V: WeightedSum,
+{
+    fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+        std::array::from_fn(|i| {
+            V::weighted_sum(&terms.iter().map(|(v, w)| (&v[i], *w)).collect::<Vec<_>>())
+        })
+    }
+}
+
+macro_rules! impl_weighted_sum_for_tuple {
+    ($(($($v:ident $i:tt),*)),*) => {
+        $(
+            impl<$($v),*> WeightedSum for ($($v,)*)
+            where
+                $($v: WeightedSum),*
+            {
+                fn weighted_sum(terms: &[(&Self, f64)]) -> Self {
+                    ($($v::weighted_sum(&terms.iter().map(|(v, w)| (&v.$i, *w)).collect::<Vec<_>>()),)*)
+                }
+            }
+        )*
+    };
+}
+
+impl_weighted_sum_for_tuple!(
+    (A 0),
+    (A 0, B 1),
+    (A 0, B 1, C 2),
+    (A 0, B 1, C 2, D 3)
+);
+
+impl Interpolator {
+    /// Interpolate the value at the point as the weighted sum of the values of the natural neighbors (see [WeightedSum]).
+    /// If the point is outside the triangulation, None is returned unless an extrapolation mode is set.
+    ///
+    /// The result is the same as `interpolate` except for the rounding error.
+    pub fn interpolate_weighted<P, V>(
+        &self,
+        values: &[V],
+        ptarget: P,
+    ) -> Result<Option<V>, InterpolatorError>
+    where
+        P: Into<Point> + Clone,
+        V: WeightedSum,
+    {
+        if self.site_count() != values.len() {
+            return Err(InterpolatorError::DifferentNumberOfPointsAndValues);
+        }
+
+        let mut weights = Vec::new();
+        self.perform_interpoation::<P>(ptarget, &mut |i, weight, _, _| {
+            weights.push((i, weight));
+        })?;
+
+        let weight_sum = compensated_sum(weights.iter().map(|(_, w)| *w));
+        if weight_sum == 0. {
+            return Ok(None);
+        }
+        let terms = weights
+            .iter()
+            .map(|(i, w)| (&values[*i], w / weight_sum))
+            .collect::<Vec<_>>();
+        Ok(Some(V::weighted_sum(&terms)))
+    }
+}
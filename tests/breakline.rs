@@ -0,0 +1,129 @@
+use naturalneighbor::{Interpolator, InterpolatorError, Point};
+use rand::Rng;
+
+/// the sites on both sides of the breakline along x = 50, and the sites on the breakline
+fn sites(rng: &mut rand::rngs::StdRng, n: usize) -> (Vec<Point>, Vec<(usize, usize)>) {
+    let mut points = vec![
+        Point { x: 50.0, y: -10.0 },
+        Point { x: 50.0, y: 110.0 },
+        Point { x: 50.0, y: 30.0 },
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 100.0, y: 0.0 },
+        Point { x: 100.0, y: 100.0 },
+        Point { x: 0.0, y: 100.0 },
+    ];
+    for _ in 0..n {
+        let x = rng.gen::<f64>() * 100.0;
+        if x != 50.0 {
+            points.push(Point {
+                x,
+                y: rng.gen::<f64>() * 100.0,
+            });
+        }
+    }
+    (points, vec![(0, 1)])
+}
+
+/// the weights of the sites on the far side of the breakline are zero
+fn check_barrier(interpolator: &Interpolator, points: &[Point], rng: &mut rand::rngs::StdRng) {
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        };
+        let weights = interpolator
+            .query_weights(ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+        for (i, w) in weights {
+            let far = (points[i].x - 50.0) * (ptarget.x - 50.0) < 0.0;
+            assert!(!far || w == 0.0, "{:?}: {} {}", ptarget, i, w);
+            assert!(w >= 0.0, "{:?}: {} {}", ptarget, i, w);
+        }
+    }
+}
+
+#[test]
+fn breakline_barrier() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let (points, breaklines) = sites(&mut rng, 1000);
+
+    // the sites on the far side are natural neighbors without the breakline
+    let interpolator = Interpolator::new(&points);
+    let weights = interpolator
+        .query_weights(Point { x: 49.9, y: 50.0 })
+        .unwrap()
+        .unwrap();
+    assert!(weights.iter().any(|(i, _)| points[*i].x > 50.0));
+
+    let interpolator = Interpolator::new_with_breaklines(&points, &breaklines).unwrap();
+    // the breakline is split at the site on it
+    assert_eq!(interpolator.breaklines(), vec![(0, 2), (1, 2)]);
+    check_barrier(&interpolator, &points, &mut rng);
+
+    // the point on the breakline is interpolated linearly along it
+    let values = points.iter().map(|p| p.y).collect::<Vec<_>>();
+    let value = interpolator
+        .interpolate(&values, Point { x: 50.0, y: 70.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 70.0).abs() < 1e-9);
+}
+
+/// the breaklines are kept while the sites are inserted and removed
+#[test]
+fn breakline_insert_remove() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let (points, breaklines) = sites(&mut rng, 500);
+
+    let mut interpolator = Interpolator::new_with_breaklines(&points[..50], &breaklines).unwrap();
+    for p in &points[50..] {
+        interpolator.insert(p.clone()).unwrap();
+    }
+    check_barrier(&interpolator, &points, &mut rng);
+
+    let expected = Interpolator::new_with_breaklines(&points, &breaklines).unwrap();
+    let values = points.iter().map(|p| p.x * p.y).collect::<Vec<_>>();
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        };
+        let value = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+        let expected = expected.interpolate(&values, ptarget.clone()).unwrap();
+        assert!((value.unwrap() - expected.unwrap()).abs() < 1e-6);
+    }
+
+    // the site inserted on the breakline splits it, and the breakline is joined again after it is removed
+    let index = interpolator.insert(Point { x: 50.0, y: 80.0 }).unwrap();
+    assert_eq!(
+        interpolator.breaklines(),
+        vec![(0, 2), (1, index), (2, index)]
+    );
+    interpolator.remove(index).unwrap();
+    interpolator.remove(2).unwrap();
+    assert_eq!(interpolator.breaklines(), vec![(0, 1)]);
+    for i in (7..points.len()).step_by(2) {
+        interpolator.remove(i).unwrap();
+    }
+    check_barrier(&interpolator, &points, &mut rng);
+}
+
+#[test]
+fn breakline_errors() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    assert!(matches!(
+        Interpolator::new_with_breaklines(&points, &[(0, 2), (1, 3)]),
+        Err(InterpolatorError::CrossingBreaklines((1, 3), (0, 2)))
+    ));
+    assert!(matches!(
+        Interpolator::new_with_breaklines(&points, &[(0, 4)]),
+        Err(InterpolatorError::InvalidSiteIndex(4))
+    ));
+}
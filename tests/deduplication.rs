@@ -0,0 +1,106 @@
+use naturalneighbor::{Interpolator, InterpolatorError, MergePolicy, Point};
+use rand::Rng;
+
+fn random_points(rng: &mut rand::rngs::StdRng, n: usize, bound: f64) -> Vec<Point> {
+    (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect()
+}
+
+/// check the merged sites are interpolated as one site with the average value
+#[test]
+fn deduplication_average() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let bound = 100.0;
+    let sites = random_points(&mut rng, n, bound);
+    let site_values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    // each site is duplicated with a small offset
+    let mut points = sites.clone();
+    let mut values = site_values.clone();
+    for (site, value) in sites.iter().zip(site_values.iter()) {
+        points.push(Point {
+            x: site.x + 1e-7,
+            y: site.y - 1e-7,
+        });
+        values.push(value + 1.0);
+    }
+
+    let interpolator =
+        Interpolator::new_with_deduplication(&points, 1e-6, MergePolicy::Average).unwrap();
+    let merged = interpolator.merged_sites();
+    for i in 0..n {
+        assert_eq!(merged[i], i);
+        assert_eq!(merged[i + n], i);
+    }
+
+    let average = site_values.iter().map(|v| v + 0.5).collect::<Vec<_>>();
+    let expected = Interpolator::new(&sites);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+        let value = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+        let expected = expected.interpolate(&average, ptarget.clone()).unwrap();
+        match (value, expected) {
+            (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-6),
+            (None, None) => {}
+            _ => panic!("{:?}: {:?} != {:?}", ptarget, value, expected),
+        }
+    }
+}
+
+#[test]
+fn deduplication_keep_first() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+    ];
+    let values = [0.0f64, 1.0, 2.0, 1.0, 100.0];
+
+    // the exact duplicates are merged by default
+    let mut interpolator = Interpolator::new(&points);
+    assert_eq!(interpolator.merged_sites(), &[0, 1, 2, 3, 2]);
+    let value = interpolator
+        .interpolate(&values, Point { x: 0.5, y: 0.5 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.0).abs() < 1e-6);
+
+    // the merged site takes the place of the removed site
+    interpolator.remove(2).unwrap();
+    assert_eq!(interpolator.merged_sites()[4], 4);
+    let weights = interpolator
+        .query_weights(Point { x: 1.0, y: 1.0 })
+        .unwrap()
+        .unwrap();
+    assert_eq!(weights, vec![(4, 1.0)]);
+
+    // the inserted site is merged too
+    let index = interpolator.insert(Point { x: 1.0, y: 1.0 }).unwrap();
+    assert_eq!(interpolator.merged_sites()[index], 4);
+}
+
+#[test]
+fn deduplication_error() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 1.0, y: 0.1 },
+    ];
+    assert!(matches!(
+        Interpolator::new_with_deduplication(&points, 0.2, MergePolicy::Error),
+        Err(InterpolatorError::DuplicatedSites(1, 3))
+    ));
+    assert!(Interpolator::new_with_deduplication(&points, 0.05, MergePolicy::Error).is_ok());
+}
@@ -0,0 +1,91 @@
+use naturalneighbor::{Interpolator, Point};
+
+fn grid(n: usize) -> (Vec<Point>, Vec<f64>) {
+    let points = (0..n * n)
+        .map(|i| Point {
+            x: (i % n) as f64,
+            y: (i / n) as f64,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(|p| 2.0 * p.x + p.y).collect::<Vec<_>>();
+    (points, values)
+}
+
+/// the weight of the site is 1 if the point is exactly on it
+#[test]
+fn on_site() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 0.0 },
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 0.0, y: 10.0 },
+        Point { x: 4.0, y: 6.0 },
+    ];
+    let interpolator = Interpolator::new(&points);
+
+    for (i, p) in points.iter().enumerate() {
+        let weights = interpolator.query_weights(p.clone()).unwrap().unwrap();
+        assert_eq!(weights, vec![(i, 1.0)]);
+    }
+}
+
+/// the point on the convex hull is interpolated linearly between the endpoints of the edge
+#[test]
+fn on_hull_edge() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 0.0 },
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 0.0, y: 10.0 },
+        Point { x: 4.0, y: 6.0 },
+    ];
+    let values = [0.0f64, 1.0, 2.0, 3.0, 4.0];
+    let interpolator = Interpolator::new(&points);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: 2.5, y: 0.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 0.25).abs() < 1e-12);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: 10.0, y: 7.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.7).abs() < 1e-12);
+}
+
+/// cocircular and collinear sites (a regular grid), with the points on the sites and the edges
+#[test]
+fn cocircular() {
+    let n = 20;
+    let (points, values) = grid(n);
+    let mut interpolator = Interpolator::new(&points);
+
+    let check = |interpolator: &Interpolator| {
+        for iy in 0..(n - 1) * 4 + 1 {
+            for ix in 0..(n - 1) * 4 + 1 {
+                let p = Point {
+                    x: ix as f64 * 0.25,
+                    y: iy as f64 * 0.25,
+                };
+                let value = interpolator.interpolate(&values, p.clone()).unwrap();
+                if let Some(value) = value {
+                    assert!(
+                        (value - (2.0 * p.x + p.y)).abs() < 1e-9,
+                        "{:?}: {}",
+                        p,
+                        value
+                    );
+                }
+            }
+        }
+    };
+    check(&interpolator);
+
+    // remove the sites keeping many cocircular sites
+    for i in (0..n * n).step_by(3) {
+        interpolator.remove(i).unwrap();
+    }
+    check(&interpolator);
+}
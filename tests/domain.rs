@@ -0,0 +1,232 @@
+use naturalneighbor::{Domain, ExtrapolationMode, Interpolator, Location, Point};
+use rand::Rng;
+
+fn square(min: f64, max: f64) -> Vec<Point> {
+    vec![
+        Point { x: min, y: min },
+        Point { x: max, y: min },
+        Point { x: max, y: max },
+        Point { x: min, y: max },
+    ]
+}
+
+fn random_point(rng: &mut rand::rngs::StdRng) -> Point {
+    Point {
+        x: rng.gen::<f64>() * 100.0,
+        y: rng.gen::<f64>() * 100.0,
+    }
+}
+
+#[test]
+fn domain_with_hole() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let points = (0..1000)
+        .map(|_| random_point(&mut rng))
+        .collect::<Vec<_>>();
+
+    let domain = Domain::new(&square(10.0, 90.0)).with_hole(&square(40.0, 60.0));
+    let mut interpolator = Interpolator::new(&points).with_domain(domain.clone());
+
+    // the site inserted outside the domain is never a natural neighbor
+    let mut points = points;
+    let index = interpolator.insert(Point { x: 50.0, y: 50.0 }).unwrap();
+    points.push(Point { x: 50.0, y: 50.0 });
+    assert_eq!(index, points.len() - 1);
+    let values = points.iter().map(|p| p.x + p.y).collect::<Vec<_>>();
+
+    for _ in 0..1000 {
+        let ptarget = random_point(&mut rng);
+        let weights = interpolator.query_weights(ptarget.clone()).unwrap();
+        if domain.contains(&ptarget) {
+            let weights = weights.unwrap();
+            assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+            for (i, w) in weights {
+                assert!(
+                    w == 0.0 || domain.contains(&points[i]),
+                    "{:?}: {}",
+                    ptarget,
+                    i
+                );
+            }
+        } else {
+            assert!(weights.is_none());
+            assert_eq!(interpolator.locate(ptarget.clone()), Location::Outside);
+            assert!(interpolator
+                .interpolate(&values, ptarget)
+                .unwrap()
+                .is_none());
+        }
+    }
+}
+
+/// the points outside the domain are extrapolated by the extrapolation mode
+#[test]
+fn domain_extrapolation() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 0.0 },
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 0.0, y: 10.0 },
+    ];
+    let values = [0.0f64, 1.0, 2.0, 3.0];
+    let interpolator = Interpolator::new(&points)
+        .with_domain(Domain::new(&square(0.0, 10.0)).with_hole(&square(4.0, 6.0)))
+        .with_extrapolation_mode(ExtrapolationMode::NearestSite);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: 5.5, y: 5.9 })
+        .unwrap();
+    assert_eq!(value, Some(2.0));
+
+    // the points on the boundary of the hole are inside the domain
+    let value = interpolator
+        .interpolate(&values, Point { x: 5.0, y: 4.0 })
+        .unwrap()
+        .unwrap();
+    let expected = Interpolator::new(&points)
+        .interpolate(&values, Point { x: 5.0, y: 4.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - expected).abs() < 1e-12);
+}
+
+/// the points in a hole are extrapolated from the boundary of the hole, not from the convex hull
+#[test]
+fn domain_hole_extrapolation() {
+    let points = (0..121)
+        .map(|i| Point {
+            x: (i % 11) as f64 * 10.0,
+            y: (i / 11) as f64 * 10.0,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+    let domain = Domain::new(&square(0.0, 100.0)).with_hole(&square(40.0, 60.0));
+
+    for mode in [
+        ExtrapolationMode::NearestSite,
+        ExtrapolationMode::NearestHullEdge,
+        ExtrapolationMode::GhostPoints,
+    ] {
+        let interpolator = Interpolator::new(&points)
+            .with_domain(domain.clone())
+            .with_extrapolation_mode(mode);
+
+        let ptarget = Point { x: 55.0, y: 50.0 };
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((40.0..=60.0).contains(&value), "{:?}: {}", mode, value);
+        let weights = interpolator.query_weights(ptarget).unwrap().unwrap();
+        assert!(weights.iter().all(|(i, _)| domain.contains(&points[*i])));
+
+        if mode == ExtrapolationMode::NearestSite {
+            continue;
+        }
+        // continuous across the boundary of the hole
+        let inside = interpolator
+            .interpolate(
+                &values,
+                Point {
+                    x: 60.0 + 1e-7,
+                    y: 45.0,
+                },
+            )
+            .unwrap()
+            .unwrap();
+        let outside = interpolator
+            .interpolate(
+                &values,
+                Point {
+                    x: 60.0 - 1e-7,
+                    y: 45.0,
+                },
+            )
+            .unwrap()
+            .unwrap();
+        assert!((inside - outside).abs() < 1e-5, "{:?}", mode);
+    }
+
+    let value = Interpolator::new(&points)
+        .with_domain(domain)
+        .with_extrapolation_mode(ExtrapolationMode::NearestHullEdge)
+        .interpolate(&values, Point { x: 55.0, y: 50.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 60.0).abs() < 1e-9);
+}
+
+fn circle(center: &Point, radius: f64, n: usize) -> Vec<Point> {
+    (0..n)
+        .map(|k| {
+            let angle = std::f64::consts::TAU * k as f64 / n as f64;
+            Point {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        })
+        .collect()
+}
+
+/// the nearest point on the ring, by scanning all the edges
+fn nearest_on_ring(ring: &[Point], p: &Point) -> Point {
+    let distance_2 = |q: &Point| (q.x - p.x).powi(2) + (q.y - p.y).powi(2);
+    (0..ring.len())
+        .map(|k| {
+            let (a, b) = (&ring[k], &ring[(k + 1) % ring.len()]);
+            let t = ((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y))
+                / ((b.x - a.x).powi(2) + (b.y - a.y).powi(2));
+            let t = t.clamp(0.0, 1.0);
+            Point {
+                x: a.x + (b.x - a.x) * t,
+                y: a.y + (b.y - a.y) * t,
+            }
+        })
+        .min_by(|q, r| distance_2(q).total_cmp(&distance_2(r)))
+        .unwrap()
+}
+
+/// check the domain with many vertices on its boundary
+#[test]
+fn domain_detailed_boundary() {
+    let center = Point { x: 50.0, y: 50.0 };
+    let (outer, hole) = (circle(&center, 45.0, 20000), circle(&center, 15.0, 5000));
+    let domain = Domain::new(&outer).with_hole(&hole);
+
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    for _ in 0..10000 {
+        let p = random_point(&mut rng);
+        let r = ((p.x - center.x).powi(2) + (p.y - center.y).powi(2)).sqrt();
+        if (r - 45.0).abs() < 1e-3 || (r - 15.0).abs() < 1e-3 {
+            continue;
+        }
+        assert_eq!(domain.contains(&p), (15.0..45.0).contains(&r), "{:?}", p);
+    }
+
+    // the points outside the domain take the values at the nearest point on the boundary
+    let points = (0..121)
+        .map(|i| Point {
+            x: (i % 11) as f64 * 10.0,
+            y: (i / 11) as f64 * 10.0,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(|p| p.x).collect::<Vec<_>>();
+    let interpolator = Interpolator::new(&points)
+        .with_domain(domain)
+        .with_extrapolation_mode(ExtrapolationMode::NearestHullEdge);
+    for angle in [0.3f64, 1.7, 2.9, 4.4] {
+        for (r, ring) in [(70.0, &outer), (5.0, &hole)] {
+            let ptarget = Point {
+                x: center.x + r * angle.cos(),
+                y: center.y + r * angle.sin(),
+            };
+            let pboundary = nearest_on_ring(ring, &ptarget);
+            let value = interpolator.interpolate(&values, ptarget).unwrap().unwrap();
+            let expected = interpolator
+                .interpolate(&values, pboundary)
+                .unwrap()
+                .unwrap();
+            assert!((value - expected).abs() < 1e-9, "{} != {}", value, expected);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use naturalneighbor::{GradientEstimation, Interpolator, MergePolicy, Point};
+use rand::Rng;
+
+// A macro for comparing floating point values.
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {
+        assert!(($a - $b).abs() < 1e-6);
+    };
+}
+
+/// check the gradients of a linear function are estimated exactly
+#[test]
+fn estimate_gradients() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = points
+        .iter()
+        .map(|p| 3.0 * p.x - 0.5 * p.y + 7.0)
+        .collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    for estimation in [
+        GradientEstimation::FirstRing,
+        GradientEstimation::SecondRingOnHull,
+    ] {
+        let gradients = interpolator
+            .estimate_gradients(&values, estimation)
+            .unwrap();
+        assert_eq!(gradients.len(), n);
+        for g in gradients {
+            assert_approx_eq!(g[0], 3.0);
+            assert_approx_eq!(g[1], -0.5);
+        }
+    }
+
+    // the estimated gradients can be used for the C1 interpolation
+    let values = points
+        .iter()
+        .map(|p| (p.x / 20.0).sin() + (p.y / 30.0).cos())
+        .collect::<Vec<_>>();
+    let gradients = interpolator
+        .estimate_gradients(&values, GradientEstimation::SecondRingOnHull)
+        .unwrap();
+    let ptarget = Point { x: 50.0, y: 50.0 };
+    let value = interpolator
+        .interpolate_c1(&values, &gradients, ptarget.clone())
+        .unwrap()
+        .unwrap();
+    assert!((value - ((ptarget.x / 20.0).sin() + (ptarget.y / 30.0).cos())).abs() < 1e-2);
+}
+
+/// check the duplicated sites take the gradient of the site they are merged into
+#[test]
+fn estimate_gradients_duplicated() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let n = 100;
+    let mut points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    for i in 0..10 {
+        points.push(points[i * 7].clone());
+    }
+    let f = |p: &Point| 3.0 * p.x - 0.5 * p.y + 7.0;
+    let values = points.iter().map(f).collect::<Vec<_>>();
+
+    let interpolator =
+        Interpolator::new_with_deduplication(&points, 1e-9, MergePolicy::Average).unwrap();
+    let gradients = interpolator
+        .estimate_gradients(&values, GradientEstimation::SecondRingOnHull)
+        .unwrap();
+    assert_eq!(gradients.len(), points.len());
+    for g in &gradients {
+        assert_approx_eq!(g[0], 3.0);
+        assert_approx_eq!(g[1], -0.5);
+    }
+
+    // the C1 surface is not flattened around the duplicated sites
+    for i in 0..10 {
+        let p = &points[i * 7];
+        let ptarget = Point {
+            x: p.x + 0.1,
+            y: p.y - 0.05,
+        };
+        let value = interpolator
+            .interpolate_c1(&values, &gradients, ptarget.clone())
+            .unwrap();
+        if let Some(value) = value {
+            assert_approx_eq!(value, f(&ptarget));
+        }
+    }
+}
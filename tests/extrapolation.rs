@@ -0,0 +1,192 @@
+use naturalneighbor::{ExtrapolationMode, Interpolator, Point};
+use rand::Rng;
+
+// A macro for comparing floating point values.
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr) => {
+        assert!(($a - $b).abs() < 1e-6);
+    };
+}
+
+fn square() -> ([Point; 5], [f64; 5]) {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 0.0 },
+        Point { x: 10.0, y: 10.0 },
+        Point { x: 0.0, y: 10.0 },
+        Point { x: 4.0, y: 6.0 },
+    ];
+    let values = [0.0, 1.0, 2.0, 3.0, 4.0];
+    (points, values)
+}
+
+#[test]
+fn extrapolation_none() {
+    let (points, values) = square();
+    let interpolator = Interpolator::new(&points);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: -1.0, y: 5.0 })
+        .unwrap();
+    assert!(value.is_none());
+}
+
+#[test]
+fn extrapolation_nearest_site() {
+    let (points, values) = square();
+    let interpolator =
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::NearestSite);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: 12.0, y: 11.0 })
+        .unwrap()
+        .unwrap();
+    assert_approx_eq!(value, 2.0);
+
+    // the inside of the convex hull is not affected
+    let inside = interpolator
+        .interpolate(&values, Point { x: 3.0, y: 4.0 })
+        .unwrap()
+        .unwrap();
+    let expected = Interpolator::new(&points)
+        .interpolate(&values, Point { x: 3.0, y: 4.0 })
+        .unwrap()
+        .unwrap();
+    assert_approx_eq!(inside, expected);
+}
+
+#[test]
+fn extrapolation_nearest_hull_edge() {
+    let (points, values) = square();
+    let interpolator =
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::NearestHullEdge);
+
+    let value = interpolator
+        .interpolate(&values, Point { x: 2.5, y: -3.0 })
+        .unwrap()
+        .unwrap();
+    assert_approx_eq!(value, 0.25);
+
+    let weights = interpolator
+        .query_weights(Point { x: -5.0, y: 2.0 })
+        .unwrap()
+        .unwrap();
+    assert_eq!(weights.len(), 2);
+    assert_approx_eq!(weights.iter().map(|(_, w)| w).sum::<f64>(), 1.0);
+}
+
+#[test]
+fn extrapolation_ghost_points() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator =
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::GhostPoints);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound * 3.0 - bound,
+            y: rng.gen::<f64>() * bound * 3.0 - bound,
+        };
+        let weights = interpolator
+            .query_weights(ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert_approx_eq!(weights.iter().map(|(_, w)| w).sum::<f64>(), 1.0);
+        assert!(weights.iter().all(|(_, w)| *w >= 0.0));
+
+        let value = interpolator.interpolate(&values, ptarget).unwrap().unwrap();
+        assert_approx_eq!(
+            value,
+            weights.iter().map(|(i, w)| values[*i] * w).sum::<f64>()
+        );
+    }
+}
+
+/// the values extrapolated with the ghost points are continuous across the convex hull
+#[test]
+fn extrapolation_ghost_points_continuity() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    let values = [0.0f64, 1.0, 2.0, 1.0];
+    let interpolator =
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::GhostPoints);
+    let inside = interpolator
+        .interpolate(
+            &values,
+            Point {
+                x: 0.999999,
+                y: 0.3,
+            },
+        )
+        .unwrap()
+        .unwrap();
+    let outside = interpolator
+        .interpolate(
+            &values,
+            Point {
+                x: 1.000001,
+                y: 0.3,
+            },
+        )
+        .unwrap()
+        .unwrap();
+    assert!((inside - outside).abs() < 1e-5);
+
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let points = (0..300)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(|p| 2.0 * p.x - p.y).collect::<Vec<_>>();
+    let hull = Interpolator::new(&points);
+    let interpolator =
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::GhostPoints);
+
+    for k in 0..500 {
+        let angle = std::f64::consts::TAU * k as f64 / 500.0;
+        let ray = |t: f64| Point {
+            x: 50.0 + angle.cos() * t,
+            y: 50.0 + angle.sin() * t,
+        };
+        // find where the ray crosses the convex hull
+        let (mut lower, mut upper) = (0.0, 100.0);
+        for _ in 0..60 {
+            let t = (lower + upper) / 2.0;
+            if hull.interpolate(&values, ray(t)).unwrap().is_some() {
+                lower = t;
+            } else {
+                upper = t;
+            }
+        }
+        let inside = interpolator
+            .interpolate(&values, ray(lower - 1e-7))
+            .unwrap()
+            .unwrap();
+        let outside = interpolator
+            .interpolate(&values, ray(upper + 1e-7))
+            .unwrap()
+            .unwrap();
+        assert!(
+            (inside - outside).abs() < 1e-5,
+            "{:?}: {} != {}",
+            ray(lower),
+            inside,
+            outside
+        );
+    }
+}
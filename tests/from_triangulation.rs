@@ -0,0 +1,169 @@
+use delaunator::EMPTY;
+use naturalneighbor::{Interpolator, InterpolatorError, Point};
+use rand::Rng;
+
+/// check the interpolator from the triangulation gives the same results as the one triangulating the points
+#[test]
+fn from_triangulation() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let triangulation = delaunator::triangulate(&points);
+    let interpolator = Interpolator::from_triangulation(
+        &points,
+        &triangulation.triangles,
+        &triangulation.halfedges,
+    )
+    .unwrap();
+    let expected = Interpolator::new(&points);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        };
+        assert_eq!(
+            interpolator.interpolate(&values, ptarget.clone()).unwrap(),
+            expected.interpolate(&values, ptarget).unwrap()
+        );
+    }
+}
+
+#[test]
+fn from_triangulation_errors() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 1.0, y: 0.9 },
+    ];
+    let triangulation = delaunator::triangulate(&points);
+    let (triangles, halfedges) = (&triangulation.triangles, &triangulation.halfedges);
+    assert!(Interpolator::from_triangulation(&points, triangles, halfedges).is_ok());
+
+    // the halfedge is not paired
+    let mut broken = halfedges.clone();
+    let e = broken.iter().position(|h| *h != EMPTY).unwrap();
+    broken[e] = EMPTY;
+    assert!(matches!(
+        Interpolator::from_triangulation(&points, triangles, &broken),
+        Err(InterpolatorError::InvalidHarfedge(_))
+    ));
+
+    // the triangle refers to a site which does not exist
+    let mut broken = triangles.clone();
+    broken[0] = 5;
+    assert!(matches!(
+        Interpolator::from_triangulation(&points, &broken, halfedges),
+        Err(InterpolatorError::InvalidSiteIndex(5))
+    ));
+
+    // the triangles are counterclockwise
+    let reversed = triangles
+        .chunks(3)
+        .flat_map(|t| [t[0], t[2], t[1]])
+        .collect::<Vec<_>>();
+    let reversed_halfedges = (0..halfedges.len())
+        .map(|e| {
+            // the edge k of the reversed triangle is the edge 2 - k of the original one
+            let original = e / 3 * 3 + [2, 1, 0][e % 3];
+            match halfedges[original] {
+                EMPTY => EMPTY,
+                h => h / 3 * 3 + [2, 1, 0][h % 3],
+            }
+        })
+        .collect::<Vec<_>>();
+    assert!(matches!(
+        Interpolator::from_triangulation(&points, &reversed, &reversed_halfedges),
+        Err(InterpolatorError::InvalidTriangle(_))
+    ));
+
+    // the site (1.0, 1.2) is inside the circumcircle of the triangle on the edge from (0.0, 0.0) to (2.0, 2.0)
+    let square = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 1.0, y: 1.2 },
+    ];
+    let triangles = [0, 2, 1, 0, 4, 2, 0, 3, 4, 4, 3, 2];
+    let halfedges = [5, EMPTY, EMPTY, 8, 11, 0, EMPTY, 9, 3, 7, EMPTY, 4];
+    assert!(matches!(
+        Interpolator::from_triangulation(&square, &triangles, &halfedges),
+        Err(InterpolatorError::NotDelaunay(0, 4))
+    ));
+
+    // the triangle between (0.0, 2.0), (1.0, 1.0) and (0.0, 0.0) is missing
+    let fan = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 1.0, y: 1.0 },
+    ];
+    let triangles = [0, 4, 1, 1, 4, 2, 2, 4, 3];
+    let halfedges = [EMPTY, 3, EMPTY, 1, 6, EMPTY, 4, EMPTY, EMPTY];
+    assert!(matches!(
+        Interpolator::from_triangulation(&fan, &triangles, &halfedges),
+        Err(InterpolatorError::NonConvexHull(4))
+    ));
+}
+
+#[test]
+fn from_triangulation_incomplete() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 1.0, y: 1.1 },
+    ];
+
+    // the site (1.0, 1.1) is left out of the triangulation of the square
+    let triangles = [0, 2, 1, 0, 3, 2];
+    let halfedges = [5, EMPTY, EMPTY, EMPTY, EMPTY, 0];
+    assert!(matches!(
+        Interpolator::from_triangulation(&points, &triangles, &halfedges),
+        Err(InterpolatorError::UnusedSite(4))
+    ));
+
+    // the separate triangles have their own convex hulls
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 5.0, y: 5.0 },
+        Point { x: 6.0, y: 5.0 },
+        Point { x: 5.0, y: 6.0 },
+    ];
+    let triangles = [0, 2, 1, 3, 5, 4];
+    let halfedges = [EMPTY; 6];
+    assert!(matches!(
+        Interpolator::from_triangulation(&points, &triangles, &halfedges),
+        Err(InterpolatorError::NonConvexHull(_))
+    ));
+
+    // the duplicated points are merged into the site in the triangulation
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 2.0, y: 0.0 },
+        Point { x: 2.0, y: 2.0 },
+        Point { x: 0.0, y: 2.0 },
+        Point { x: 2.0, y: 2.0 },
+    ];
+    let triangulation = delaunator::triangulate(&points);
+    assert!(Interpolator::from_triangulation(
+        &points,
+        &triangulation.triangles,
+        &triangulation.halfedges
+    )
+    .is_ok());
+}
@@ -0,0 +1,119 @@
+use naturalneighbor::{Interpolator, InterpolatorError, Point};
+use rand::Rng;
+
+/// check the interpolator updated by `insert` gives the same results as the one built from scratch
+#[test]
+fn insert() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    // start from a few sites and insert the rest one by one (both inside and outside the convex hull)
+    let mut interpolator = Interpolator::new(&points[..3]);
+    for (i, p) in points.iter().enumerate().skip(3) {
+        assert_eq!(interpolator.insert(p.clone()).unwrap(), i);
+    }
+
+    let expected = Interpolator::new(&points);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+        let value = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+        let expected = expected.interpolate(&values, ptarget.clone()).unwrap();
+        match (value, expected) {
+            (Some(value), Some(expected)) => assert!(
+                (value - expected).abs() < 1e-6,
+                "{:?}: {} != {}",
+                ptarget,
+                value,
+                expected
+            ),
+            (None, None) => {}
+            _ => panic!("{:?}: {:?} != {:?}", ptarget, value, expected),
+        }
+    }
+}
+
+#[test]
+fn insert_duplicated() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+    ];
+    let mut interpolator = Interpolator::new(&points);
+    let index = interpolator.insert(Point { x: 1.0, y: 1.0 }).unwrap();
+    assert_eq!(index, 4);
+
+    let values = [0.0f64, 1.0, 1.0, 2.0, 100.0];
+    let value = interpolator
+        .interpolate(&values, Point { x: 0.5, y: 0.5 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn insert_into_collinear() {
+    let points = [Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }];
+    let mut interpolator = Interpolator::new(&points);
+    interpolator.insert(Point { x: 2.0, y: 0.0 }).unwrap();
+    interpolator.insert(Point { x: 0.0, y: 2.0 }).unwrap();
+
+    let values = [0.0f64, 1.0, 2.0, 2.0];
+    let value = interpolator
+        .interpolate(&values, Point { x: 0.5, y: 0.5 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn insert_non_finite() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 1.0, y: 1.0 },
+    ];
+    let values = [0.0f64, 1.0, 1.0, 2.0, 1.0];
+    for mut interpolator in [
+        Interpolator::new(&points),
+        Interpolator::new_periodic(&points, 2.0, 2.0),
+    ] {
+        for p in [
+            Point {
+                x: f64::NAN,
+                y: 0.5,
+            },
+            Point {
+                x: 0.5,
+                y: f64::INFINITY,
+            },
+        ] {
+            assert!(matches!(
+                interpolator.insert(p),
+                Err(InterpolatorError::NonFiniteCoordinate(4))
+            ));
+        }
+
+        // the interpolator is unchanged
+        assert_eq!(interpolator.insert(Point { x: 0.5, y: 0.5 }).unwrap(), 4);
+        let value = interpolator
+            .interpolate(&values, Point { x: 0.5, y: 0.5 })
+            .unwrap()
+            .unwrap();
+        assert!((value - 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,84 @@
+use naturalneighbor::{CoordinateKind, Interpolator, Point};
+use rand::Rng;
+
+fn quadratic(p: &Point) -> f64 {
+    1.0 + 2.0 * p.x - p.y + 0.5 * p.x * p.x - 0.3 * p.x * p.y + 0.2 * p.y * p.y
+}
+
+fn quadratic_gradient(p: &Point) -> [f64; 2] {
+    [2.0 + p.x - 0.3 * p.y, -1.0 - 0.3 * p.x + 0.4 * p.y]
+}
+
+/// check `interpolate_c1` reproduces quadratic functions
+#[test]
+fn interpolate_c1() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 10.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = points.iter().map(quadratic).collect::<Vec<_>>();
+    let gradients = points.iter().map(quadratic_gradient).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+        let value = interpolator
+            .interpolate_c1(&values, &gradients, ptarget.clone())
+            .unwrap_or_else(|e| panic!("Failed to interpolate {:?} with error {:?}", ptarget, e));
+
+        if let Some(value) = value {
+            let expected = quadratic(&ptarget);
+            assert!(
+                (value - expected).abs() < 1e-6,
+                "{:?}: {} != {}",
+                ptarget,
+                value,
+                expected
+            );
+        }
+    }
+}
+
+/// check `interpolate_c1` uses Sibson coordinates regardless of the coordinate kind
+#[test]
+fn interpolate_c1_laplace() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let n = 100;
+    let bound = 10.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = points.iter().map(quadratic).collect::<Vec<_>>();
+    let gradients = points.iter().map(quadratic_gradient).collect::<Vec<_>>();
+
+    let sibson = Interpolator::new(&points);
+    let laplace = Interpolator::new(&points).with_coordinate_kind(CoordinateKind::Laplace);
+
+    for _ in 0..100 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+        let expected = sibson
+            .interpolate_c1(&values, &gradients, ptarget.clone())
+            .unwrap();
+        let value = laplace
+            .interpolate_c1(&values, &gradients, ptarget.clone())
+            .unwrap();
+        assert_eq!(value, expected, "{:?}", ptarget);
+    }
+}
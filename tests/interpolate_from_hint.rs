@@ -0,0 +1,69 @@
+use naturalneighbor::{Interpolator, Point, QueryHint};
+use rand::Rng;
+
+/// check `interpolate_from_hint` gives the same results as `interpolate`
+#[test]
+fn interpolate_from_hint() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let mut values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let mut interpolator = Interpolator::new(&points);
+    let mut hint = QueryHint::new();
+
+    check(&interpolator, &values, &mut hint, &mut rng);
+
+    // the hint is still usable after the triangulation is updated
+    for i in 0..n / 2 {
+        interpolator.remove(i * 2).unwrap();
+    }
+    check(&interpolator, &values, &mut hint, &mut rng);
+
+    for _ in 0..n {
+        interpolator
+            .insert(Point {
+                x: rng.gen::<f64>() * bound,
+                y: rng.gen::<f64>() * bound,
+            })
+            .unwrap();
+        values.push(rng.gen::<f64>());
+    }
+    check(&interpolator, &values, &mut hint, &mut rng);
+}
+
+fn check(
+    interpolator: &Interpolator,
+    values: &[f64],
+    hint: &mut QueryHint,
+    rng: &mut rand::rngs::StdRng,
+) {
+    let bound = 100.0;
+    // a random walk, sometimes jumping far away or outside the convex hull
+    let mut ptarget = Point {
+        x: bound / 2.0,
+        y: bound / 2.0,
+    };
+    for _ in 0..2000 {
+        if rng.gen::<f64>() < 0.05 {
+            ptarget = Point {
+                x: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+                y: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+            };
+        } else {
+            ptarget.x += rng.gen::<f64>() * 2.0 - 1.0;
+            ptarget.y += rng.gen::<f64>() * 2.0 - 1.0;
+        }
+        let value = interpolator
+            .interpolate_from_hint(values, ptarget.clone(), hint)
+            .unwrap();
+        let expected = interpolator.interpolate(values, ptarget.clone()).unwrap();
+        assert_eq!(value, expected, "{:?}", ptarget);
+    }
+}
@@ -0,0 +1,46 @@
+use naturalneighbor::{GridSpec, Interpolator, Point};
+use rand::Rng;
+
+/// check the result of `interpolate_grid` is the same as `interpolate` at each point
+#[test]
+fn interpolate_grid() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    // the grid is larger than the convex hull
+    let grid = GridSpec {
+        origin: Point { x: -5.0, y: -5.0 },
+        dx: 0.55,
+        dy: 0.45,
+        nx: 200,
+        ny: 250,
+    };
+    let result = interpolator
+        .interpolate_grid(&values, grid.clone())
+        .unwrap();
+    assert_eq!(result.len(), grid.nx * grid.ny);
+
+    for iy in 0..grid.ny {
+        for ix in 0..grid.nx {
+            let expected = interpolator
+                .interpolate(&values, grid.point(ix, iy))
+                .unwrap();
+            let value = result[iy * grid.nx + ix];
+            match (value, expected) {
+                (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-9),
+                (None, None) => {}
+                _ => panic!("({}, {}): {:?} != {:?}", ix, iy, value, expected),
+            }
+        }
+    }
+}
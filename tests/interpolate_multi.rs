@@ -0,0 +1,45 @@
+use naturalneighbor::{ExtrapolationMode, Interpolator, Point};
+use rand::Rng;
+
+/// check the fields interpolated at once are the same as the ones interpolated one by one
+#[test]
+fn interpolate_multi() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    let fields = (0..3)
+        .map(|_| (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let fields = fields.iter().map(|f| f.as_slice()).collect::<Vec<_>>();
+
+    for interpolator in [
+        Interpolator::new(&points),
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::NearestSite),
+    ] {
+        for _ in 0..500 {
+            let ptarget = Point {
+                x: -10.0 + rng.gen::<f64>() * 120.0,
+                y: -10.0 + rng.gen::<f64>() * 120.0,
+            };
+            let values = interpolator
+                .interpolate_multi(&fields, ptarget.clone())
+                .unwrap();
+            let expected = fields
+                .iter()
+                .map(|f| interpolator.interpolate(f, ptarget.clone()).unwrap())
+                .collect::<Option<Vec<_>>>();
+            assert_eq!(values, expected);
+        }
+    }
+
+    let interpolator = Interpolator::new(&points);
+    let short = &fields[1][1..];
+    assert!(interpolator
+        .interpolate_multi(&[fields[0], short, fields[2]], Point { x: 50.0, y: 50.0 })
+        .is_err());
+}
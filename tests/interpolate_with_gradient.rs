@@ -0,0 +1,124 @@
+use naturalneighbor::{CoordinateKind, ExtrapolationMode, Interpolator, Point};
+use rand::Rng;
+
+/// check the gradient of `interpolate_with_gradient` matches the finite difference of `interpolate`
+#[test]
+fn interpolate_with_gradient() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    for mode in [ExtrapolationMode::None, ExtrapolationMode::GhostPoints] {
+        let interpolator = Interpolator::new(&points).with_extrapolation_mode(mode);
+
+        let h = 1e-6;
+        let mut checked = 0;
+        for _ in 0..1000 {
+            let ptarget = Point {
+                x: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+                y: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+            };
+            let result = interpolator
+                .interpolate_with_gradient(&values, ptarget.clone())
+                .unwrap();
+            let (value, gradient) = if let Some(result) = result {
+                result
+            } else {
+                continue;
+            };
+
+            let expected = interpolator
+                .interpolate(&values, ptarget.clone())
+                .unwrap()
+                .unwrap();
+            assert!((value - expected).abs() < 1e-6);
+
+            let value_at = |dx: f64, dy: f64| {
+                interpolator
+                    .interpolate(
+                        &values,
+                        Point {
+                            x: ptarget.x + dx,
+                            y: ptarget.y + dy,
+                        },
+                    )
+                    .unwrap()
+            };
+            if let (Some(xp), Some(xm), Some(yp), Some(ym)) = (
+                value_at(h, 0.0),
+                value_at(-h, 0.0),
+                value_at(0.0, h),
+                value_at(0.0, -h),
+            ) {
+                let fd = [(xp - xm) / (2.0 * h), (yp - ym) / (2.0 * h)];
+                assert!(
+                    (gradient[0] - fd[0]).abs() < 1e-3 && (gradient[1] - fd[1]).abs() < 1e-3,
+                    "{:?}: {:?} != {:?}",
+                    ptarget,
+                    gradient,
+                    fd
+                );
+                checked += 1;
+            }
+        }
+        assert!(checked > 500);
+    }
+}
+
+/// the gradient is finite on the lines through the sites, and zero at the sites
+#[test]
+fn interpolate_with_gradient_degenerate() {
+    let points = (0..25)
+        .map(|i| Point {
+            x: (i % 5) as f64,
+            y: (i / 5) as f64,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(|p| p.x * p.y).collect::<Vec<_>>();
+
+    for kind in [CoordinateKind::Sibson, CoordinateKind::Laplace] {
+        let interpolator = Interpolator::new(&points)
+            .with_coordinate_kind(kind)
+            .with_extrapolation_mode(ExtrapolationMode::GhostPoints);
+
+        for k in 0..=60 {
+            let t = k as f64 / 10.0 - 1.0;
+            for ptarget in [
+                Point { x: t, y: 0.0 },
+                Point { x: t, y: 2.0 },
+                Point { x: t, y: t },
+                Point { x: t, y: 4.0 - t },
+                Point { x: t, y: -1.0 },
+            ] {
+                let (value, gradient) = interpolator
+                    .interpolate_with_gradient(&values, ptarget.clone())
+                    .unwrap()
+                    .unwrap();
+                assert!(
+                    value.is_finite() && gradient.iter().all(|g| g.is_finite()),
+                    "{:?} {:?}: {} {:?}",
+                    kind,
+                    ptarget,
+                    value,
+                    gradient
+                );
+            }
+        }
+
+        for (p, v) in points.iter().zip(&values) {
+            let (value, gradient) = interpolator
+                .interpolate_with_gradient(&values, p.clone())
+                .unwrap()
+                .unwrap();
+            assert_eq!((value, gradient), (*v, [0.0, 0.0]));
+        }
+    }
+}
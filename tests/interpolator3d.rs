@@ -0,0 +1,77 @@
+use naturalneighbor::{Interpolator3D, Point3D};
+use rand::Rng;
+
+fn random_point(rng: &mut rand::rngs::StdRng, min: f64, max: f64) -> Point3D {
+    Point3D {
+        x: min + rng.gen::<f64>() * (max - min),
+        y: min + rng.gen::<f64>() * (max - min),
+        z: min + rng.gen::<f64>() * (max - min),
+    }
+}
+
+fn linear(p: &Point3D) -> f64 {
+    2.0 * p.x - p.y + 3.0 * p.z + 1.0
+}
+
+/// Sibson coordinates reproduce linear functions
+#[test]
+fn interpolate_3d_linear() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let points = (0..2000)
+        .map(|_| random_point(&mut rng, 0.0, 100.0))
+        .collect::<Vec<_>>();
+    let values = points.iter().map(linear).collect::<Vec<_>>();
+
+    let interpolator = Interpolator3D::new(&points);
+
+    for _ in 0..200 {
+        let ptarget = random_point(&mut rng, 20.0, 80.0);
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!(
+            (value - linear(&ptarget)).abs() < 1e-6,
+            "{:?}: {} != {}",
+            ptarget,
+            value,
+            linear(&ptarget)
+        );
+
+        let weights = interpolator.query_weights(ptarget).unwrap().unwrap();
+        assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weights.iter().all(|(_, w)| *w > 0.0));
+    }
+}
+
+#[test]
+fn interpolate_3d_on_site_and_outside() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let mut points = (0..500)
+        .map(|_| random_point(&mut rng, 0.0, 1.0))
+        .collect::<Vec<_>>();
+    // the duplicated site is ignored
+    points.push(points[10].clone());
+    let interpolator = Interpolator3D::new(&points);
+
+    for (i, p) in points.iter().enumerate().take(100) {
+        let weights = interpolator.query_weights(p.clone()).unwrap().unwrap();
+        assert_eq!(weights, vec![(i, 1.0)]);
+    }
+
+    let values = vec![1.0f64; points.len()];
+    assert!(interpolator
+        .interpolate(
+            &values,
+            Point3D {
+                x: 1.5,
+                y: 0.5,
+                z: 0.5
+            }
+        )
+        .unwrap()
+        .is_none());
+    assert!(interpolator
+        .interpolate(&values[1..], [0.5, 0.5, 0.5])
+        .is_err());
+}
@@ -0,0 +1,110 @@
+use naturalneighbor::{CoordinateKind, ExtrapolationMode, Interpolator, Point};
+use rand::Rng;
+
+/// check Laplace coordinates reproduce linear functions and their gradients
+#[test]
+fn laplace() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = points
+        .iter()
+        .map(|p| 0.3 * p.x - 0.7 * p.y + 2.0)
+        .collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points).with_coordinate_kind(CoordinateKind::Laplace);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+
+        let weights = interpolator.query_weights(ptarget.clone()).unwrap();
+        let weights = if let Some(weights) = weights {
+            weights
+        } else {
+            continue;
+        };
+        let value = weights.iter().map(|(i, w)| values[*i] * w).sum::<f64>();
+        let expected = 0.3 * ptarget.x - 0.7 * ptarget.y + 2.0;
+        assert!(
+            (value - expected).abs() < 1e-6,
+            "{:?}: {} != {}",
+            ptarget,
+            value,
+            expected
+        );
+
+        let (value, gradient) = interpolator
+            .interpolate_with_gradient(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((value - expected).abs() < 1e-6);
+        assert!((gradient[0] - 0.3).abs() < 1e-6);
+        assert!((gradient[1] + 0.7).abs() < 1e-6);
+    }
+}
+
+/// check the gradient of the Laplace coordinates outside the convex hull
+#[test]
+fn laplace_ghost_points() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 100;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points)
+        .with_coordinate_kind(CoordinateKind::Laplace)
+        .with_extrapolation_mode(ExtrapolationMode::GhostPoints);
+
+    let h = 1e-6;
+    for _ in 0..100 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound * 1.8 - bound * 0.4,
+            y: -rng.gen::<f64>() * bound * 0.4,
+        };
+        let (_, gradient) = interpolator
+            .interpolate_with_gradient(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+
+        let value_at = |dx: f64, dy: f64| {
+            interpolator
+                .interpolate(
+                    &values,
+                    Point {
+                        x: ptarget.x + dx,
+                        y: ptarget.y + dy,
+                    },
+                )
+                .unwrap()
+                .unwrap()
+        };
+        let fd = [
+            (value_at(h, 0.0) - value_at(-h, 0.0)) / (2.0 * h),
+            (value_at(0.0, h) - value_at(0.0, -h)) / (2.0 * h),
+        ];
+        assert!(
+            (gradient[0] - fd[0]).abs() < 1e-4 && (gradient[1] - fd[1]).abs() < 1e-4,
+            "{:?}: {:?} != {:?}",
+            ptarget,
+            gradient,
+            fd
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use naturalneighbor::{Ceil, Floor, Interpolator, Point, Rounded, Truncate};
+use rand::Rng;
+
+/// the values of the other types are interpolated as well as f64
+#[test]
+fn lerpable_values() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 300;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    let values_f32 = values.iter().map(|v| *v as f32).collect::<Vec<_>>();
+    let values_features = values
+        .iter()
+        .map(|v| [*v as f32, 2.0 * *v as f32, -*v as f32, 0.5])
+        .collect::<Vec<_>>();
+    let values_tuple = values
+        .iter()
+        .map(|v| (*v, *v as f32 * 10.0))
+        .collect::<Vec<_>>();
+    let values_rgb = values
+        .iter()
+        .map(|v| [(v * 255.0) as u8, 255 - (v * 255.0) as u8, 128])
+        .collect::<Vec<_>>();
+
+    for _ in 0..200 {
+        let ptarget = Point {
+            x: 10.0 + rng.gen::<f64>() * 80.0,
+            y: 10.0 + rng.gen::<f64>() * 80.0,
+        };
+        let expected = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+
+        let value = interpolator
+            .interpolate(&values_f32, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((value as f64 - expected).abs() < 1e-5);
+
+        let value = interpolator
+            .interpolate(&values_features, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        for (v, e) in value.iter().zip([expected, 2.0 * expected, -expected, 0.5]) {
+            assert!((*v as f64 - e).abs() < 1e-5);
+        }
+
+        let value = interpolator
+            .interpolate(&values_tuple, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((value.0 - expected).abs() < 1e-9);
+        assert!((value.1 as f64 - expected * 10.0).abs() < 1e-4);
+
+        // rounded once after the values are blended
+        let value = interpolator
+            .interpolate(&values_rgb, ptarget)
+            .unwrap()
+            .unwrap();
+        assert!((value[0] as f64 - expected * 255.0).abs() < 1.5);
+        assert!((value[1] as f64 - (255.0 - expected * 255.0)).abs() < 1.5);
+        assert_eq!(value[2], 128);
+    }
+}
+
+#[test]
+fn lerpable_rounding() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 4.0, y: 0.0 },
+        Point { x: 0.0, y: 4.0 },
+    ];
+    let interpolator = Interpolator::new(&points);
+    let ptarget = Point { x: 1.0, y: 0.0 };
+
+    // -10 * 0.75 + 10 * 0.25 = -5
+    let values = [-10i32, 10, 0];
+    assert_eq!(
+        interpolator.interpolate(&values, ptarget.clone()).unwrap(),
+        Some(-5)
+    );
+
+    // -9 * 0.75 + 10 * 0.25 = -4.25
+    let values = [-9i32, 10, 0];
+    assert_eq!(
+        interpolator.interpolate(&values, ptarget.clone()).unwrap(),
+        Some(-4)
+    );
+    let rounded = values.map(Rounded::<i32, Floor>::new);
+    let value = interpolator.interpolate(&rounded, ptarget.clone()).unwrap();
+    assert_eq!(value.unwrap().value, -5);
+    let rounded = values.map(Rounded::<i32, Truncate>::new);
+    let value = interpolator.interpolate(&rounded, ptarget.clone()).unwrap();
+    assert_eq!(value.unwrap().value, -4);
+
+    // 9 * 0.75 - 10 * 0.25 = 4.25
+    let rounded = [9i32, -10, 0].map(Rounded::<i32, Ceil>::new);
+    let value = interpolator.interpolate(&rounded, ptarget).unwrap();
+    assert_eq!(value.unwrap().value, 5);
+}
+
+/// the rounding errors are not accumulated over the natural neighbors
+#[test]
+fn lerpable_rounded_once() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 0.5, y: 0.5 },
+    ];
+    let interpolator = Interpolator::new(&points);
+    let ptarget = Point { x: 0.3, y: 0.4 };
+
+    let values = [1u8, 3, 2, 2, 0];
+    let expected = interpolator
+        .interpolate(&values.map(|v| v as f64), ptarget.clone())
+        .unwrap()
+        .unwrap();
+    assert!((expected - 0.545).abs() < 1e-3);
+
+    let value = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+    assert_eq!(value, Some(1));
+    let value = interpolator
+        .interpolate_multi(&[&values], ptarget.clone())
+        .unwrap();
+    assert_eq!(value, Some(vec![1]));
+
+    let values = values.map(|v| [v, 255 - v]);
+    let value = interpolator.interpolate(&values, ptarget).unwrap();
+    assert_eq!(value, Some([1, 254]));
+}
@@ -1,4 +1,6 @@
-use naturalneighbor::{Interpolator, Point};
+#![allow(clippy::needless_range_loop)]
+
+use naturalneighbor::{Interpolator, Location, Point};
 use rand::Rng;
 
 // A macro for comparing floating point values.
@@ -83,3 +85,37 @@ fn on_edge() {
         }
     }
 }
+
+/// the points on the edges are reported with the sites at the ends of the edge
+#[test]
+fn on_edge_location() {
+    let bound = 10;
+    let points = (0..bound * bound)
+        .map(|i| Point {
+            x: (i % bound) as f64,
+            y: (i / bound) as f64,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..bound * bound).map(|i| i as f64).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    for (x, y) in [(3.5, 4.0), (6.0, 2.5), (1.5, 8.0), (8.0, 1.5)] {
+        let tp = Point { x, y };
+        let (value, location) = interpolator
+            .interpolate_with_location(&values, tp.clone())
+            .unwrap();
+
+        // the sites at the ends of the edge
+        let ifloor = (tp.y.floor() * bound as f64 + tp.x.floor()) as usize;
+        let iceil = (tp.y.ceil() * bound as f64 + tp.x.ceil()) as usize;
+        assert!(
+            location == Location::OnEdge(ifloor, iceil)
+                || location == Location::OnEdge(iceil, ifloor),
+            "{:?}: {:?}",
+            tp,
+            location
+        );
+        assert_approx_eq!(value.unwrap(), (values[ifloor] + values[iceil]) * 0.5);
+    }
+}
@@ -1,4 +1,6 @@
-use naturalneighbor::{Interpolator, Point};
+#![allow(clippy::needless_range_loop)]
+
+use naturalneighbor::{Interpolator, Location, Point};
 use rand::Rng;
 
 #[test]
@@ -57,3 +59,28 @@ fn on_point() {
         }
     }
 }
+
+#[test]
+fn on_site() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    // the value of the site is returned exactly
+    for (i, p) in points.iter().enumerate() {
+        let (value, location) = interpolator
+            .interpolate_with_location(&values, p.clone())
+            .unwrap();
+        assert_eq!(location, Location::OnSite(i));
+        assert_eq!(value, Some(values[i]));
+    }
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "rayon")]
+
+use naturalneighbor::{GridSpec, Interpolator, Point};
+use rand::Rng;
+
+/// check the parallel interpolation gives exactly the same results as the sequential one
+#[test]
+fn parallel() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 1000;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    let targets = (0..10000)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+            y: rng.gen::<f64>() * bound * 1.2 - bound * 0.1,
+        })
+        .collect::<Vec<_>>();
+    let result = interpolator
+        .par_interpolate_many(&values, &targets)
+        .unwrap();
+    for (p, value) in targets.iter().zip(result) {
+        assert_eq!(value, interpolator.interpolate(&values, p.clone()).unwrap());
+    }
+
+    let grid = GridSpec {
+        origin: Point { x: -5.0, y: -5.0 },
+        dx: 0.55,
+        dy: 0.45,
+        nx: 200,
+        ny: 250,
+    };
+    assert_eq!(
+        interpolator
+            .par_interpolate_grid(&values, grid.clone())
+            .unwrap(),
+        interpolator.interpolate_grid(&values, grid).unwrap()
+    );
+}
@@ -0,0 +1,217 @@
+use std::f64::consts::PI;
+
+use naturalneighbor::{Interpolator, InterpolatorError, Point};
+use rand::Rng;
+
+fn periodic_function(p: &Point) -> f64 {
+    (2.0 * PI * p.x / 10.0).sin() + (2.0 * PI * p.y / 10.0).cos()
+}
+
+#[test]
+fn periodic_across_edges() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 2000;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        })
+        .collect::<Vec<_>>();
+    let values = points.iter().map(periodic_function).collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new_periodic(&points, 10.0, 10.0);
+
+    for _ in 0..500 {
+        // including the points near the edges and outside the domain
+        let ptarget = Point {
+            x: -10.0 + rng.gen::<f64>() * 30.0,
+            y: -10.0 + rng.gen::<f64>() * 30.0,
+        };
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!(
+            (value - periodic_function(&ptarget)).abs() < 0.05,
+            "{:?}: {} != {}",
+            ptarget,
+            value,
+            periodic_function(&ptarget)
+        );
+
+        let weights = interpolator.query_weights(ptarget).unwrap().unwrap();
+        assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weights.iter().all(|(i, _)| *i < n));
+    }
+
+    // the same result in every period
+    for ptarget in [Point { x: 0.01, y: 9.99 }, Point { x: 5.0, y: 0.0 }] {
+        let expected = interpolator.query_weights(ptarget.clone()).unwrap();
+        for (dx, dy) in [(10.0, 0.0), (-10.0, 20.0), (30.0, -10.0)] {
+            let shifted = Point {
+                x: ptarget.x + dx,
+                y: ptarget.y + dy,
+            };
+            let weights = interpolator.query_weights(shifted).unwrap();
+            let (expected, weights) = (expected.clone().unwrap(), weights.unwrap());
+            assert_eq!(expected.len(), weights.len());
+            for ((i, w), (j, v)) in expected.iter().zip(weights.iter()) {
+                assert_eq!(i, j);
+                assert!((w - v).abs() < 1e-9);
+            }
+        }
+    }
+}
+
+/// the weights of the copies of a site are aggregated
+#[test]
+fn periodic_aggregated_weights() {
+    let points = [Point { x: 1.0, y: 1.0 }, Point { x: 2.0, y: 1.5 }];
+    let interpolator = Interpolator::new_periodic(&points, 2.0, 2.0);
+
+    let mut weights = interpolator
+        .query_weights(Point { x: 1.7, y: 0.2 })
+        .unwrap()
+        .unwrap();
+    weights.sort_by_key(|(i, _)| *i);
+    assert_eq!(weights.len(), 2);
+    assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let values = [1.0f64, 3.0];
+    let value = interpolator
+        .interpolate(&values, Point { x: 1.7, y: 0.2 })
+        .unwrap()
+        .unwrap();
+    assert!((value - (weights[0].1 + 3.0 * weights[1].1)).abs() < 1e-9);
+
+    assert!(interpolator
+        .interpolate(&[1.0f64; 18], Point { x: 1.7, y: 0.2 })
+        .is_err());
+}
+
+/// check the periodic interpolator updated by `insert` and `remove` gives the same results as the one built from the sites
+#[test]
+fn periodic_insert_remove() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let n = 200;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let mut interpolator = Interpolator::new_periodic(&points[..n - 1], 10.0, 10.0);
+    assert_eq!(interpolator.insert(points[n - 1].clone()).unwrap(), n - 1);
+    interpolator.remove(3).unwrap();
+    assert!(interpolator.remove(3).is_err());
+    assert!(interpolator.remove(n * 2).is_err());
+
+    let (rest_points, rest_values): (Vec<_>, Vec<_>) = (0..n)
+        .filter(|i| *i != 3)
+        .map(|i| (points[i].clone(), values[i]))
+        .unzip();
+    let expected = Interpolator::new_periodic(&rest_points, 10.0, 10.0);
+
+    for _ in 0..200 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        };
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        let expected = expected
+            .interpolate(&rest_values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!(
+            (value - expected).abs() < 1e-6,
+            "{:?}: {} != {}",
+            ptarget,
+            value,
+            expected
+        );
+    }
+}
+
+#[test]
+fn periodic_invalid() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([2; 32]);
+    let points = (0..100)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        })
+        .collect::<Vec<_>>();
+
+    for period in [0.0, -10.0, f64::NAN, f64::INFINITY] {
+        assert!(matches!(
+            Interpolator::try_new_periodic(&points, period, 10.0),
+            Err(InterpolatorError::InvalidPeriod(_))
+        ));
+        assert!(matches!(
+            Interpolator::try_new_periodic(&points, 10.0, period),
+            Err(InterpolatorError::InvalidPeriod(_))
+        ));
+    }
+
+    // the sites spread over two periods
+    assert!(matches!(
+        Interpolator::try_new_periodic(&points, 5.0, 10.0),
+        Err(InterpolatorError::SiteOutsidePeriod(_))
+    ));
+    assert!(matches!(
+        Interpolator::try_new_periodic(&points, 10.0, 5.0),
+        Err(InterpolatorError::SiteOutsidePeriod(_))
+    ));
+    assert!(matches!(
+        Interpolator::try_new_periodic::<Point>(&[], 10.0, 10.0),
+        Err(InterpolatorError::TooFewPoints)
+    ));
+
+    let values = points.iter().map(periodic_function).collect::<Vec<_>>();
+    let interpolator = Interpolator::try_new_periodic(&points, 10.0, 10.0).unwrap();
+    let expected = Interpolator::new_periodic(&points, 10.0, 10.0);
+    for _ in 0..100 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 30.0 - 10.0,
+            y: rng.gen::<f64>() * 30.0 - 10.0,
+        };
+        assert_eq!(
+            interpolator.interpolate(&values, ptarget.clone()).unwrap(),
+            expected.interpolate(&values, ptarget).unwrap()
+        );
+    }
+}
+
+/// the inserted site is wrapped into the domain of one period
+#[test]
+fn periodic_insert_wrapped() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([3; 32]);
+    let points = (0..100)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        })
+        .collect::<Vec<_>>();
+
+    let mut wrapped = Interpolator::new_periodic(&points, 10.0, 10.0);
+    let mut expected = wrapped.clone();
+    wrapped.insert(Point { x: 25.0, y: -7.5 }).unwrap();
+    expected.insert(Point { x: 5.0, y: 2.5 }).unwrap();
+
+    let mut values = points.iter().map(periodic_function).collect::<Vec<_>>();
+    values.push(1.0);
+    for _ in 0..100 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * 10.0,
+            y: rng.gen::<f64>() * 10.0,
+        };
+        let value = wrapped.interpolate(&values, ptarget.clone()).unwrap();
+        let expected = expected.interpolate(&values, ptarget).unwrap();
+        assert!((value.unwrap() - expected.unwrap()).abs() < 1e-9);
+    }
+}
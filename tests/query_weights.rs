@@ -1,3 +1,5 @@
+#![allow(clippy::needless_range_loop)]
+
 use naturalneighbor::{Interpolator, Point};
 use rand::Rng;
 
@@ -0,0 +1,89 @@
+use naturalneighbor::{Interpolator, InterpolatorError, Point};
+use rand::Rng;
+
+/// check the interpolator updated by `remove` gives the same results as the one built from the rest of the sites
+#[test]
+fn remove() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let bound = 100.0;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+
+    let mut interpolator = Interpolator::new(&points);
+    let mut removed = vec![false; n];
+    for _ in 0..n / 2 {
+        let i = rng.gen_range(0..n);
+        if removed[i] {
+            assert!(interpolator.remove(i).is_err());
+            continue;
+        }
+        interpolator.remove(i).unwrap();
+        removed[i] = true;
+    }
+
+    let (rest_points, rest_values): (Vec<_>, Vec<_>) = (0..n)
+        .filter(|i| !removed[*i])
+        .map(|i| (points[i].clone(), values[i]))
+        .unzip();
+    let expected = Interpolator::new(&rest_points);
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: rng.gen::<f64>() * bound,
+            y: rng.gen::<f64>() * bound,
+        };
+        let value = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+        let expected = expected.interpolate(&rest_values, ptarget.clone()).unwrap();
+        match (value, expected) {
+            (Some(value), Some(expected)) => assert!(
+                (value - expected).abs() < 1e-6,
+                "{:?}: {} != {}",
+                ptarget,
+                value,
+                expected
+            ),
+            (None, None) => {}
+            _ => panic!("{:?}: {:?} != {:?}", ptarget, value, expected),
+        }
+    }
+}
+
+#[test]
+fn remove_and_insert() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    let mut interpolator = Interpolator::new(&points);
+
+    // remove a site on the convex hull
+    interpolator.remove(2).unwrap();
+    assert!(interpolator.is_vacant(2));
+    let values = [0.0f64, 1.0, 100.0, 1.0];
+    assert!(interpolator
+        .interpolate(&values, Point { x: 0.8, y: 0.8 })
+        .unwrap()
+        .is_none());
+
+    // the index of the removed site is not reused
+    assert_eq!(interpolator.insert(Point { x: 1.0, y: 1.0 }).unwrap(), 4);
+    let values = [0.0f64, 1.0, 100.0, 1.0, 2.0];
+    let value = interpolator
+        .interpolate(&values, Point { x: 0.5, y: 0.5 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.0).abs() < 1e-6);
+
+    assert!(matches!(
+        interpolator.remove(5),
+        Err(InterpolatorError::InvalidSiteIndex(5))
+    ));
+}
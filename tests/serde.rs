@@ -0,0 +1,172 @@
+#![cfg(feature = "serde")]
+
+use naturalneighbor::{Domain, ExtrapolationMode, Interpolator, MergePolicy, Point};
+use rand::Rng;
+
+/// check the loaded interpolator gives the same results as the original one
+#[test]
+fn serde_roundtrip() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let mut points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    // a duplicated site
+    points.push(points[7].clone());
+
+    let domain = Domain::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 100.0, y: 0.0 },
+        Point { x: 100.0, y: 100.0 },
+        Point { x: 0.0, y: 100.0 },
+    ])
+    .with_hole(&[
+        Point { x: 40.0, y: 40.0 },
+        Point { x: 60.0, y: 40.0 },
+        Point { x: 60.0, y: 60.0 },
+        Point { x: 40.0, y: 60.0 },
+    ]);
+    let mut interpolator =
+        Interpolator::new_with_deduplication(&points, 1e-9, MergePolicy::Average)
+            .unwrap()
+            .with_extrapolation_mode(ExtrapolationMode::NearestSite)
+            .with_domain(domain);
+    interpolator.remove(3).unwrap();
+
+    let json = serde_json::to_string(&interpolator).unwrap();
+    let loaded: Interpolator = serde_json::from_str(&json).unwrap();
+
+    for _ in 0..1000 {
+        let ptarget = Point {
+            x: -10.0 + rng.gen::<f64>() * 120.0,
+            y: -10.0 + rng.gen::<f64>() * 120.0,
+        };
+        let weights = interpolator.query_weights(ptarget.clone()).unwrap();
+        let loaded_weights = loaded.query_weights(ptarget.clone()).unwrap();
+        match (weights, loaded_weights) {
+            (Some(weights), Some(loaded_weights)) => {
+                assert_eq!(weights.len(), loaded_weights.len());
+                for ((i, w), (j, v)) in weights.iter().zip(loaded_weights.iter()) {
+                    assert_eq!(i, j);
+                    assert!((w - v).abs() < 1e-9);
+                }
+            }
+            (None, None) => {}
+            (weights, loaded_weights) => panic!("{:?} != {:?}", weights, loaded_weights),
+        }
+    }
+    assert!(loaded.is_vacant(3));
+    assert_eq!(loaded.merged_sites()[n], 7);
+}
+
+#[test]
+fn serde_invalid_triangulation() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    let interpolator = Interpolator::new(&points);
+    let mut json = serde_json::to_value(&interpolator).unwrap();
+
+    let harfedges = json["harfedges"].as_array().unwrap().clone();
+    let paired = harfedges
+        .iter()
+        .position(|h| h.as_u64().unwrap() < harfedges.len() as u64)
+        .unwrap();
+
+    // the harfedge is not paired
+    let mut broken = json.clone();
+    broken["harfedges"][paired] = serde_json::json!(paired);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the triangle refers to a site which does not exist
+    let mut broken = json.clone();
+    broken["triangles"][0] = serde_json::json!(4);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the triangles are counterclockwise in the mirrored points
+    let mut broken = json.clone();
+    for p in broken["points"].as_array_mut().unwrap() {
+        p[0] = serde_json::json!(-p[0].as_f64().unwrap());
+    }
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    let mut broken = json.clone();
+    broken["degree_limitation"] = serde_json::json!(0);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the triangle does not share the edge with the opposite triangle
+    json["triangles"].as_array_mut().unwrap().reverse();
+    assert!(serde_json::from_value::<Interpolator>(json).is_err());
+
+    let interpolator = Interpolator::new_periodic(&points, 2.0, 2.0);
+    let mut broken = serde_json::to_value(&interpolator).unwrap();
+    broken["period"][0] = serde_json::json!(0.0);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+}
+
+#[test]
+fn serde_invalid_sites() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 1.0 },
+        Point { x: 0.0, y: 1.0 },
+        Point { x: 0.5, y: 0.5 },
+        Point { x: 0.5, y: 0.5 },
+        Point { x: 0.2, y: 0.7 },
+        Point { x: 0.5, y: 0.5 },
+    ];
+    let mut interpolator =
+        Interpolator::new_with_deduplication(&points, 1e-9, MergePolicy::Average).unwrap();
+    interpolator.remove(6).unwrap();
+    let json = serde_json::to_value(&interpolator).unwrap();
+    assert!(serde_json::from_value::<Interpolator>(json.clone()).is_ok());
+
+    // the site is merged into a removed site
+    let mut broken = json.clone();
+    broken["merged"][5] = serde_json::json!(6);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the site is merged into a merged site
+    let mut broken = json.clone();
+    broken["merged"][7] = serde_json::json!(5);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the triangles refer to a merged site
+    let replace_site = |json: &mut serde_json::Value, site: u64| {
+        for i in json["triangles"].as_array_mut().unwrap() {
+            if i.as_u64() == Some(4) {
+                *i = serde_json::json!(site);
+            }
+        }
+    };
+    let mut broken = json.clone();
+    replace_site(&mut broken, 5);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // the triangles refer to a removed site
+    let mut broken = json.clone();
+    replace_site(&mut broken, 6);
+    broken["points"][6] = serde_json::json!([0.5, 0.5]);
+    assert!(serde_json::from_value::<Interpolator>(broken).is_err());
+
+    // JSON has no NaN, so the coordinate is broken in a binary format
+    let interpolator = Interpolator::new(&[
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.375, y: 1.0 },
+    ]);
+    let bytes = bincode::serialize(&interpolator).unwrap();
+    assert!(bincode::deserialize::<Interpolator>(&bytes).is_ok());
+    let x = 0.375f64.to_le_bytes();
+    let k = bytes.windows(8).position(|w| w == x).unwrap();
+    let mut broken = bytes.clone();
+    broken[k..k + 8].copy_from_slice(&f64::NAN.to_le_bytes());
+    assert!(bincode::deserialize::<Interpolator>(&broken).is_err());
+}
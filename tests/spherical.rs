@@ -0,0 +1,99 @@
+use naturalneighbor::{Point, SphericalInterpolator};
+use rand::Rng;
+
+fn random_point(rng: &mut rand::rngs::StdRng, min_lat: f64, max_lat: f64) -> Point {
+    // uniform on the sphere between the latitudes
+    let (smin, smax) = (min_lat.to_radians().sin(), max_lat.to_radians().sin());
+    Point {
+        x: -180.0 + rng.gen::<f64>() * 360.0,
+        y: (smin + rng.gen::<f64>() * (smax - smin))
+            .asin()
+            .to_degrees(),
+    }
+}
+
+fn smooth(p: &Point) -> f64 {
+    let (lon, lat) = (p.x.to_radians(), p.y.to_radians());
+    lat.cos() * lon.cos() + 2.0 * lat.cos() * lon.sin() + 3.0 * lat.sin()
+}
+
+#[test]
+fn spherical_global() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let points = (0..3000)
+        .map(|_| random_point(&mut rng, -90.0, 90.0))
+        .collect::<Vec<_>>();
+    let values = points.iter().map(smooth).collect::<Vec<_>>();
+
+    let interpolator = SphericalInterpolator::new(&points);
+
+    for _ in 0..500 {
+        let ptarget = random_point(&mut rng, -90.0, 90.0);
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!(
+            (value - smooth(&ptarget)).abs() < 0.05,
+            "{:?}: {} != {}",
+            ptarget,
+            value,
+            smooth(&ptarget)
+        );
+
+        let weights = interpolator.query_weights(ptarget).unwrap().unwrap();
+        assert!((weights.iter().map(|(_, w)| w).sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!(weights.iter().all(|(_, w)| *w > 0.0));
+    }
+
+    // no seams at the antimeridian and the poles
+    for (p, q) in [
+        (Point { x: 180.0, y: 12.0 }, Point { x: -180.0, y: 12.0 }),
+        (
+            Point {
+                x: 179.9999,
+                y: -40.0,
+            },
+            Point {
+                x: -179.9999,
+                y: -40.0,
+            },
+        ),
+        (Point { x: 0.0, y: 90.0 }, Point { x: 123.0, y: 90.0 }),
+        (Point { x: -45.0, y: -90.0 }, Point { x: 90.0, y: -90.0 }),
+    ] {
+        let vp = interpolator.interpolate(&values, p).unwrap().unwrap();
+        let vq = interpolator.interpolate(&values, q).unwrap().unwrap();
+        assert!((vp - vq).abs() < 1e-4, "{} != {}", vp, vq);
+    }
+}
+
+#[test]
+fn spherical_on_site_and_hemisphere() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([1; 32]);
+    let mut points = (0..500)
+        .map(|_| random_point(&mut rng, 20.0, 90.0))
+        .collect::<Vec<_>>();
+    // the duplicated site is ignored
+    points.push(points[10].clone());
+    let interpolator = SphericalInterpolator::new(&points);
+
+    for (i, p) in points.iter().enumerate().take(100) {
+        let weights = interpolator.query_weights(p.clone()).unwrap().unwrap();
+        assert_eq!(weights, vec![(i, 1.0)]);
+    }
+
+    let values = vec![1.0f64; points.len()];
+    let value = interpolator
+        .interpolate(&values, Point { x: 170.0, y: 60.0 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 1.0).abs() < 1e-9);
+    assert!(interpolator
+        .interpolate(&values, Point { x: 10.0, y: -30.0 })
+        .unwrap()
+        .is_none());
+    assert!(interpolator
+        .interpolate(&values[1..], Point { x: 10.0, y: 60.0 })
+        .is_err());
+}
@@ -0,0 +1,125 @@
+use naturalneighbor::{ExtrapolationMode, Interpolator, InterpolatorError, Location, Point};
+
+#[test]
+fn try_new_errors() {
+    let points = [Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 0.0 }];
+    assert!(matches!(
+        Interpolator::try_new(&points),
+        Err(InterpolatorError::TooFewPoints)
+    ));
+
+    // the duplicated points are counted once
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+    ];
+    assert!(matches!(
+        Interpolator::try_new(&points),
+        Err(InterpolatorError::TooFewPoints)
+    ));
+
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 2.0 },
+        Point { x: 2.0, y: 4.0 },
+        Point { x: -3.0, y: -6.0 },
+    ];
+    assert!(matches!(
+        Interpolator::try_new(&points),
+        Err(InterpolatorError::Collinear)
+    ));
+
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point {
+            x: f64::NAN,
+            y: 1.0,
+        },
+        Point {
+            x: 0.0,
+            y: f64::INFINITY,
+        },
+    ];
+    assert!(matches!(
+        Interpolator::try_new(&points),
+        Err(InterpolatorError::NonFiniteCoordinate(2))
+    ));
+
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    assert!(Interpolator::try_new(&points).is_ok());
+}
+
+/// the collinear sites are interpolated linearly along the line
+#[test]
+fn linear_fallback() {
+    let points = [
+        Point { x: 4.0, y: 2.0 },
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 10.0, y: 5.0 },
+        Point { x: 2.0, y: 1.0 },
+    ];
+    let mut values = vec![4.0f64, 0.0, 10.0, 2.0];
+
+    assert!(matches!(
+        Interpolator::try_new_with_linear_fallback(&points[..1]),
+        Err(InterpolatorError::TooFewPoints)
+    ));
+
+    let mut interpolator = Interpolator::try_new_with_linear_fallback(&points).unwrap();
+
+    for i in 0..=100 {
+        let x = i as f64 * 0.1;
+        // the points off the line are projected on it
+        for offset in [-1.0, 0.0, 2.0] {
+            let p = Point {
+                x: x - offset,
+                y: x / 2.0 + offset * 2.0,
+            };
+            let value = interpolator.interpolate(&values, p).unwrap().unwrap();
+            assert!((value - x).abs() < 1e-9, "{}: {}", x, value);
+        }
+    }
+
+    assert_eq!(
+        interpolator.locate(Point { x: 2.0, y: 1.0 }),
+        Location::OnSite(3)
+    );
+    assert_eq!(
+        interpolator.locate(Point { x: 3.0, y: 1.5 }),
+        Location::OnHullEdge(3, 0)
+    );
+
+    // the points beyond the ends are not interpolated by default
+    let p = Point { x: 12.0, y: 6.0 };
+    assert_eq!(interpolator.locate(p.clone()), Location::Outside);
+    assert!(interpolator
+        .interpolate(&values, p.clone())
+        .unwrap()
+        .is_none());
+
+    interpolator = interpolator.with_extrapolation_mode(ExtrapolationMode::NearestSite);
+    let value = interpolator.interpolate(&values, p).unwrap().unwrap();
+    assert!((value - 10.0).abs() < 1e-9);
+
+    // the sites are triangulated once a site off the line is inserted
+    let index = interpolator.insert(Point { x: 0.0, y: 5.0 }).unwrap();
+    values.push(0.0);
+    assert!(matches!(
+        interpolator.locate(Point { x: 1.0, y: 2.0 }),
+        Location::Inside
+    ));
+
+    // and interpolated linearly again after it is removed
+    interpolator.remove(index).unwrap();
+    let value = interpolator
+        .interpolate(&values, Point { x: 3.0, y: 1.5 })
+        .unwrap()
+        .unwrap();
+    assert!((value - 3.0).abs() < 1e-9);
+}
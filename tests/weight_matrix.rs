@@ -0,0 +1,68 @@
+use naturalneighbor::{ExtrapolationMode, Interpolator, Point};
+use rand::Rng;
+
+/// the sparse mat-vec gives the same results as `interpolate` for every set of the values
+#[test]
+fn weight_matrix_apply() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    // including the points outside the triangulation
+    let queries = (0..300)
+        .map(|_| Point {
+            x: -20.0 + rng.gen::<f64>() * 140.0,
+            y: -20.0 + rng.gen::<f64>() * 140.0,
+        })
+        .collect::<Vec<_>>();
+
+    for interpolator in [
+        Interpolator::new(&points),
+        Interpolator::new(&points).with_extrapolation_mode(ExtrapolationMode::NearestSite),
+    ] {
+        let matrix = interpolator.weight_matrix(&queries).unwrap();
+        assert_eq!(matrix.rows(), queries.len());
+        assert_eq!(matrix.columns(), n);
+
+        for (r, query) in queries.iter().enumerate() {
+            let weights = interpolator.query_weights(query.clone()).unwrap();
+            let row = matrix.row(r).collect::<Vec<_>>();
+            assert_eq!(weights.unwrap_or_default(), row);
+        }
+
+        for _ in 0..3 {
+            let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+            let interpolated = matrix.apply(&values).unwrap();
+            for (query, value) in queries.iter().zip(interpolated) {
+                let expected = interpolator.interpolate(&values, query.clone()).unwrap();
+                match (value, expected) {
+                    (Some(value), Some(expected)) => assert!((value - expected).abs() < 1e-9),
+                    (value, expected) => assert_eq!(value, expected),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn weight_matrix_invalid_values() {
+    let points = [
+        Point { x: 0.0, y: 0.0 },
+        Point { x: 1.0, y: 0.0 },
+        Point { x: 0.0, y: 1.0 },
+    ];
+    let interpolator = Interpolator::new(&points);
+    let matrix = interpolator
+        .weight_matrix(&[Point { x: 0.2, y: 0.2 }])
+        .unwrap();
+    assert_eq!(matrix.nnz(), 3);
+    assert!(matrix.apply(&[1.0, 2.0]).is_err());
+
+    let matrix = interpolator.weight_matrix::<Point>(&[]).unwrap();
+    assert_eq!(matrix.rows(), 0);
+    assert_eq!(matrix.apply(&[1.0, 2.0, 3.0]).unwrap(), vec![]);
+}
@@ -0,0 +1,154 @@
+use std::f64::consts::PI;
+
+use naturalneighbor::{Floor, Interpolator, Point, Rounded};
+use rand::Rng;
+
+/// the weighted sum gives the same results as the sequential lerp
+#[test]
+fn weighted_sum_random() {
+    let mut rng: rand::rngs::StdRng = rand::SeedableRng::from_seed([0; 32]);
+    let n = 500;
+    let points = (0..n)
+        .map(|_| Point {
+            x: rng.gen::<f64>() * 100.0,
+            y: rng.gen::<f64>() * 100.0,
+        })
+        .collect::<Vec<_>>();
+    let values = (0..n).map(|_| rng.gen::<f64>()).collect::<Vec<_>>();
+    let values_tuple = values
+        .iter()
+        .map(|v| (*v, [*v as f32, 1.0 - *v as f32]))
+        .collect::<Vec<_>>();
+
+    let interpolator = Interpolator::new(&points);
+
+    for _ in 0..500 {
+        let ptarget = Point {
+            x: -10.0 + rng.gen::<f64>() * 120.0,
+            y: -10.0 + rng.gen::<f64>() * 120.0,
+        };
+        let expected = interpolator.interpolate(&values, ptarget.clone()).unwrap();
+        let value = interpolator
+            .interpolate_weighted(&values, ptarget.clone())
+            .unwrap();
+        let value_tuple = interpolator
+            .interpolate_weighted(&values_tuple, ptarget)
+            .unwrap();
+        match (expected, value, value_tuple) {
+            (Some(expected), Some(value), Some(value_tuple)) => {
+                assert!((value - expected).abs() < 1e-9);
+                assert!((value_tuple.0 - expected).abs() < 1e-9);
+                assert!((value_tuple.1[0] as f64 - expected).abs() < 1e-5);
+                assert!((value_tuple.1[1] as f64 - (1.0 - expected)).abs() < 1e-5);
+            }
+            (None, None, None) => {}
+            _ => panic!(),
+        }
+    }
+
+    assert!(interpolator
+        .interpolate_weighted(&values[1..], Point { x: 50.0, y: 50.0 })
+        .is_err());
+}
+
+/// the site with many neighbors: the constant value is kept and the integers are rounded once
+#[test]
+fn weighted_sum_high_degree() {
+    let n = 200;
+    let mut points = (0..n)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / n as f64 + 0.1;
+            Point {
+                x: angle.cos(),
+                y: angle.sin(),
+            }
+        })
+        .collect::<Vec<_>>();
+    points.push(Point { x: 0.0, y: 0.0 });
+    let interpolator = Interpolator::new_with_curtom_degree_limitation(&points, 1000);
+
+    let values = vec![0.1f64; n + 1];
+    let values_u8 = (0..=n).map(|i| (i % 7) as u8 * 10).collect::<Vec<_>>();
+    let values_f64 = values_u8.iter().map(|v| *v as f64).collect::<Vec<_>>();
+
+    for ptarget in [
+        Point { x: 0.01, y: 0.02 },
+        Point { x: -0.3, y: 0.2 },
+        Point { x: 0.05, y: -0.6 },
+    ] {
+        let value = interpolator
+            .interpolate_weighted(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((value - 0.1).abs() <= f64::EPSILON);
+
+        let expected = interpolator
+            .interpolate_weighted(&values_f64, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        let value = interpolator
+            .interpolate_weighted(&values_u8, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, expected.round() as u8);
+
+        let rounded = values_u8
+            .iter()
+            .map(|v| Rounded::<u8, Floor>::new(*v))
+            .collect::<Vec<_>>();
+        let value = interpolator
+            .interpolate_weighted(&rounded, ptarget)
+            .unwrap()
+            .unwrap();
+        assert_eq!(value.value, expected.floor() as u8);
+    }
+}
+
+/// `interpolate` sums up the floats by the compensated summation as well as `interpolate_weighted`
+#[test]
+fn weighted_sum_interpolate() {
+    let n = 200;
+    let mut points = (0..n)
+        .map(|i| {
+            let angle = 2.0 * PI * i as f64 / n as f64 + 0.1;
+            Point {
+                x: angle.cos(),
+                y: angle.sin(),
+            }
+        })
+        .collect::<Vec<_>>();
+    points.push(Point { x: 0.0, y: 0.0 });
+    let interpolator = Interpolator::new_with_curtom_degree_limitation(&points, 1000);
+
+    let values = vec![0.1f64; n + 1];
+    let values_f32 = vec![0.1f32; n + 1];
+    let values_random = (0..=n).map(|i| (i as f64 * 0.37).sin()).collect::<Vec<_>>();
+
+    for ptarget in [
+        Point { x: 0.01, y: 0.02 },
+        Point { x: -0.3, y: 0.2 },
+        Point { x: 0.05, y: -0.6 },
+    ] {
+        let value = interpolator
+            .interpolate(&values, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert!((value - 0.1).abs() <= f64::EPSILON);
+
+        let value = interpolator
+            .interpolate(&values_f32, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, 0.1f32);
+
+        let value = interpolator
+            .interpolate(&values_random, ptarget.clone())
+            .unwrap()
+            .unwrap();
+        let expected = interpolator
+            .interpolate_weighted(&values_random, ptarget)
+            .unwrap()
+            .unwrap();
+        assert_eq!(value, expected);
+    }
+}